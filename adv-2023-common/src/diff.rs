@@ -0,0 +1,248 @@
+//! Differential testing of an optimised solution against a slow but obvious reference.
+//!
+//! Both are fed the same generated inputs, and on the first input they disagree on the lines of
+//! that input are dropped for as long as they keep disagreeing, to report a small counterexample.
+
+use std::{
+    cell::Cell,
+    fmt::{Debug, Display},
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Once,
+    },
+};
+
+use crate::{gen::Config, Task};
+
+/// What an implementation made of an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome<T> {
+    Value(T),
+    Panic(String),
+}
+
+impl<T> Outcome<T> {
+    fn of(f: impl FnOnce() -> T) -> Self {
+        match catch_unwind(AssertUnwindSafe(f)) {
+            Ok(value) => Outcome::Value(value),
+            Err(payload) => Outcome::Panic(
+                payload
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+}
+
+/// An input that the reference and the optimised implementation disagree on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch<T> {
+    /// The seed of the generated input the mismatch was found with.
+    pub seed: u64,
+    /// The shrunk input.
+    pub input: String,
+    pub reference: Outcome<T>,
+    pub optimised: Outcome<T>,
+}
+
+impl<T: Debug> Display for Mismatch<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "mismatch for seed {}", self.seed)?;
+        writeln!(f, "reference: {:?}", self.reference)?;
+        writeln!(f, "optimised: {:?}", self.optimised)?;
+        write!(f, "input:\n{}", self.input)
+    }
+}
+
+impl<T: Debug> std::error::Error for Mismatch<T> {}
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The number of threads running [`quietly`].
+static QUIETING: AtomicUsize = AtomicUsize::new(0);
+
+/// Run `f` without printing the panics it catches, which are outcomes rather than failures.
+///
+/// Panics on this thread are kept quiet, and so are panics on unnamed threads, such as the ones
+/// `f` spawns, while it runs.
+fn quietly<R>(f: impl FnOnce() -> R) -> R {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let spawned =
+                || std::thread::current().name().is_none() && QUIETING.load(Ordering::SeqCst) > 0;
+            if !QUIET.with(Cell::get) && !spawned() {
+                previous(info);
+            }
+        }));
+    });
+
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            QUIET.with(|q| q.set(self.0));
+            QUIETING.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+    QUIETING.fetch_add(1, Ordering::SeqCst);
+    let _restore = Restore(QUIET.with(|q| q.replace(true)));
+    f()
+}
+
+/// Compare `reference` and `optimised` on inputs generated with `runs` seeds from `config.seed`.
+///
+/// Panics count as outcomes, so an implementation panicking where the other does not is a
+/// mismatch, while inputs both panic on are never reported. They are not printed either.
+pub fn check<T: PartialEq>(
+    generate: impl Fn(&Config) -> String,
+    config: &Config,
+    runs: u64,
+    reference: impl Fn(&str) -> T,
+    optimised: impl Fn(&str) -> T,
+) -> Result<(), Mismatch<T>> {
+    quietly(|| {
+        let outcomes = |input: &str| {
+            (
+                Outcome::of(|| reference(input)),
+                Outcome::of(|| optimised(input)),
+            )
+        };
+        for seed in config.seed..config.seed + runs {
+            let config = Config {
+                seed,
+                ..config.clone()
+            };
+            let input = generate(&config);
+            let (r, o) = outcomes(&input);
+            if r == o {
+                continue;
+            }
+            let input = shrink(&input, |input| {
+                let (r, o) = outcomes(input);
+                r != o
+            });
+            let (reference, optimised) = outcomes(&input);
+            return Err(Mismatch {
+                seed,
+                input,
+                reference,
+                optimised,
+            });
+        }
+        Ok(())
+    })
+}
+
+/// [`check`] on inputs of `size` generated both without and with edge cases, panicking with the
+/// first mismatch.
+pub fn assert_agree<T: PartialEq + Debug>(
+    generate: impl Fn(&Config) -> String,
+    size: usize,
+    runs: u64,
+    reference: impl Fn(&str) -> T,
+    optimised: impl Fn(&str) -> T,
+) {
+    for edge_cases in [false, true] {
+        let config = Config {
+            size,
+            edge_cases,
+            ..Config::default()
+        };
+        if let Err(e) = check(&generate, &config, runs, &reference, &optimised) {
+            panic!("{}", e);
+        }
+    }
+}
+
+/// Drop lines from `input` for as long as `fails` holds, first in large chunks and then one by
+/// one.
+pub fn shrink(input: &str, mut fails: impl FnMut(&str) -> bool) -> String {
+    let join = |lines: &[&str]| lines.iter().flat_map(|l| [*l, "\n"]).collect::<String>();
+    let mut lines: Vec<&str> = input.lines().collect();
+    let mut chunk = lines.len() / 2;
+    while chunk > 0 {
+        let mut removed = false;
+        let mut start = 0;
+        while start < lines.len() {
+            let end = (start + chunk).min(lines.len());
+            let candidate = [&lines[..start], &lines[end..]].concat();
+            if fails(&join(&candidate)) {
+                lines = candidate;
+                removed = true;
+            } else {
+                start = end;
+            }
+        }
+        if !removed {
+            chunk /= 2;
+        }
+    }
+    join(&lines)
+}
+
+/// Parse and process every line of `input`, as [`Task::run`] does for a file.
+pub fn feed<T: Task>(task: &mut T, input: &str) {
+    for line in crate::lines(input.as_bytes()) {
+        let input = task.parse_bytes(line);
+        task.process(input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrink_keeps_only_the_failing_lines() {
+        let input = (1..=20).map(|n| format!("{}\n", n)).collect::<String>();
+        let shrunk = shrink(&input, |s| {
+            s.lines().any(|l| l == "7") && s.lines().any(|l| l == "13")
+        });
+        assert_eq!(shrunk, "7\n13\n");
+    }
+
+    #[test]
+    fn check_reports_the_shrunk_input() {
+        let generate = |config: &Config| {
+            (0..config.size as u64)
+                .map(|n| format!("{}\n", (n * 7 + config.seed) % 100))
+                .collect::<String>()
+        };
+        let count = |input: &str| input.lines().count();
+        let capped = |input: &str| input.lines().count().min(3);
+        assert_eq!(check(generate, &Config::default(), 3, count, count), Ok(()));
+
+        let mismatch = check(generate, &Config::default(), 3, count, capped).unwrap_err();
+        assert_eq!(mismatch.seed, 0);
+        assert_eq!(mismatch.input.lines().count(), 4);
+        assert_eq!(mismatch.reference, Outcome::Value(4));
+        assert_eq!(mismatch.optimised, Outcome::Value(3));
+    }
+
+    #[test]
+    fn check_treats_panics_as_outcomes() {
+        let generate = |_: &Config| "1\n2\nx\n".to_string();
+        let parse = |input: &str| -> u64 { input.lines().map(|l| l.parse::<u64>().unwrap()).sum() };
+        let lenient =
+            |input: &str| -> u64 { input.lines().filter_map(|l| l.parse::<u64>().ok()).sum() };
+        let mismatch = check(generate, &Config::default(), 1, parse, lenient).unwrap_err();
+        assert_eq!(mismatch.input, "x\n");
+        assert!(matches!(mismatch.reference, Outcome::Panic(_)));
+        assert_eq!(mismatch.optimised, Outcome::Value(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "mismatch for seed 0")]
+    fn assert_agree_panics_with_the_mismatch() {
+        let generate = |config: &Config| format!("{}\n", config.edge_cases);
+        let lines = |input: &str| input.lines().count();
+        assert_agree(generate, 1, 1, lines, lines);
+        assert_agree(generate, 1, 1, lines, |input| input.len());
+    }
+}
@@ -0,0 +1,443 @@
+//! Seeded generators for the input of every day, for stress testing beyond the examples.
+//!
+//! The same [`Config`] always generates the same input.
+
+use std::{collections::BTreeSet, fmt::Write};
+
+/// A small xorshift generator, good enough for test inputs and stable across platforms.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // splitmix the seed so that small seeds do not start out mostly zero
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        Self((z ^ (z >> 31)) | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `0..n`, or `0` if `n` is `0`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            0
+        } else {
+            self.next_u64() % n
+        }
+    }
+
+    /// A number in `lo..=hi`.
+    pub fn range(&mut self, lo: u64, hi: u64) -> u64 {
+        lo + self.below(hi - lo + 1)
+    }
+
+    /// `true` once in `n` times on average.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// How big the input is, in the day's natural unit such as lines, games or grid width.
+    pub size: usize,
+    pub seed: u64,
+    /// Mix in the awkward cases each day is known for, such as numbers at the edge of the grid.
+    pub edge_cases: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            size: 100,
+            seed: 0,
+            edge_cases: false,
+        }
+    }
+}
+
+/// The days that have a generator.
+pub const DAYS: [u8; 7] = [1, 2, 3, 4, 5, 6, 7];
+
+/// Generate the input of `day`, or `None` if there is no generator for it.
+pub fn generate(day: u8, config: &Config) -> Option<String> {
+    let gen = match day {
+        1 => calibration,
+        2 => games,
+        3 => schematic,
+        4 => scratchcards,
+        5 => almanac,
+        6 => races,
+        7 => hands,
+        _ => return None,
+    };
+    Some(gen(config))
+}
+
+static DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Day 1: `size` lines of letters, digits and spelled out digits.
+pub fn calibration(config: &Config) -> String {
+    let mut rng = Rng::new(config.seed);
+    let mut out = String::with_capacity(config.size * 32);
+    for _ in 0..config.size {
+        if config.edge_cases && rng.one_in(10) {
+            let line = rng.pick(&["twone", "eightwo", "oneight", "7", "sevenine", "nodigits"]);
+            writeln!(out, "{}", line).unwrap();
+            continue;
+        }
+        let parts = rng.range(1, 8);
+        let mut has_digit = false;
+        for i in 0..parts {
+            match rng.below(3) {
+                // apart from the edge cases, every line needs a real digit for part 1
+                _ if i == parts - 1 && !has_digit => out.push((b'1' + rng.below(9) as u8) as char),
+                0 => out.push_str(DIGIT_WORDS[rng.below(9) as usize]),
+                1 => {
+                    has_digit = true;
+                    out.push((b'1' + rng.below(9) as u8) as char);
+                }
+                _ => {
+                    for _ in 0..rng.range(1, 4) {
+                        out.push((b'a' + rng.below(26) as u8) as char);
+                    }
+                }
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Day 2: `size` games of pulls from red, green and blue cubes.
+pub fn games(config: &Config) -> String {
+    let mut rng = Rng::new(config.seed);
+    let mut out = String::with_capacity(config.size * 128);
+    for id in 1..=config.size {
+        write!(out, "Game {}:", id).unwrap();
+        let edge = config.edge_cases && rng.one_in(5);
+        let pulls = if edge { 1 } else { rng.range(1, 6) };
+        for pull in 0..pulls {
+            if pull > 0 {
+                out.push(';');
+            }
+            let mut first = true;
+            for (colour, limit) in [("red", 12), ("green", 13), ("blue", 14)] {
+                if rng.one_in(4) {
+                    continue;
+                }
+                // sit right on the limits of part 1 for edge cases
+                let n = if edge {
+                    rng.range(limit, limit + 1)
+                } else {
+                    rng.range(1, 20)
+                };
+                write!(out, "{} {} {}", if first { "" } else { "," }, n, colour).unwrap();
+                first = false;
+            }
+            if first {
+                out.push_str(" 1 red");
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Day 3: a `size` by `size` schematic of numbers and symbols.
+pub fn schematic(config: &Config) -> String {
+    let mut rng = Rng::new(config.seed);
+    let size = config.size.max(3);
+    let mut grid = vec![vec![b'.'; size]; size];
+    for row in grid.iter_mut() {
+        let mut col = 0;
+        while col < size {
+            match rng.below(12) {
+                0..=1 => {
+                    let len = (rng.range(1, 3) as usize).min(size - col);
+                    for c in &mut row[col..col + len] {
+                        *c = b'0' + rng.below(10) as u8;
+                    }
+                    // leave a gap so neighbouring numbers do not merge
+                    col += len + 1;
+                }
+                2 => {
+                    row[col] = *rng.pick(b"*#+$/=%@&-");
+                    col += 1;
+                }
+                _ => col += 1,
+            }
+        }
+    }
+    if config.edge_cases {
+        // gears with two neighbours in the first and the last column, and one with three
+        let last = size - 1;
+        for (r, c, n) in [
+            (0, 0, 1),
+            (size - 2, last, last),
+            (size / 2, size / 2, size / 2),
+        ] {
+            grid[r][c] = b'*';
+            grid[r + 1][n] = b'7';
+            if r > 0 {
+                grid[r - 1][n] = b'3';
+            } else {
+                grid[r][if c == 0 { 1 } else { c - 1 }] = b'5';
+            }
+        }
+        let mid = size / 2;
+        if mid + 1 < size {
+            grid[mid][mid + 1] = b'2';
+        }
+    }
+    let mut out = String::with_capacity(size * (size + 1));
+    for row in grid {
+        out.push_str(std::str::from_utf8(&row).unwrap());
+        out.push('\n');
+    }
+    out
+}
+
+/// Day 4: `size` scratchcards with 10 winning numbers and 25 numbers each.
+///
+/// Edge cases include cards matching every winning number, which quickly overflow the number of
+/// copies in part 2 for a few hundred cards.
+pub fn scratchcards(config: &Config) -> String {
+    let mut rng = Rng::new(config.seed);
+    let mut out = String::with_capacity(config.size * 128);
+    let width = config.size.to_string().len();
+    for card in 1..=config.size {
+        let left = (config.size - card) as u64;
+        let matches = if config.edge_cases && rng.one_in(5) {
+            *rng.pick(&[0, 10])
+        } else {
+            rng.below(6)
+        };
+        // a card never wins copies of cards past the end of the table
+        let matches = matches.min(left) as usize;
+
+        let mut numbers = BTreeSet::new();
+        while numbers.len() < 35 {
+            numbers.insert(rng.range(1, 99));
+        }
+        let numbers: Vec<_> = numbers.into_iter().collect();
+        let mut order: Vec<usize> = (0..35).collect();
+        for i in (1..order.len()).rev() {
+            order.swap(i, rng.below(i as u64 + 1) as usize);
+        }
+        let winning = &order[..10];
+        let mut has: Vec<_> = winning[..matches]
+            .iter()
+            .chain(&order[10..35 - matches])
+            .collect();
+        for i in (1..has.len()).rev() {
+            has.swap(i, rng.below(i as u64 + 1) as usize);
+        }
+
+        write!(out, "Card {:>width$}:", card, width = width).unwrap();
+        for &i in winning {
+            write!(out, " {:>2}", numbers[i]).unwrap();
+        }
+        out.push_str(" |");
+        for &i in has {
+            write!(out, " {:>2}", numbers[i]).unwrap();
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Day 5: 10 seed ranges and maps of `size` ranges each.
+pub fn almanac(config: &Config) -> String {
+    const MAPS: [&str; 7] = [
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ];
+    // keep every value well inside a u32, as part 1 adds lengths to them
+    const LIMIT: u64 = 1 << 31;
+    let mut rng = Rng::new(config.seed);
+    let mut out = String::with_capacity(config.size * 7 * 32);
+    let size = config.size.max(1) as u64;
+    let span = LIMIT / size;
+
+    out.push_str("seeds:");
+    for _ in 0..10 {
+        let start = rng.below(LIMIT / 2);
+        write!(out, " {} {}", start, rng.range(1, LIMIT / 64)).unwrap();
+    }
+    out.push('\n');
+
+    for map in MAPS {
+        write!(out, "\n{} map:\n", map).unwrap();
+        // source ranges partition the number line into one slot per range, to never overlap
+        for slot in 0..size {
+            let src = if config.edge_cases && slot > 0 && rng.one_in(3) {
+                slot * span
+            } else {
+                slot * span + rng.below(span / 2)
+            };
+            let len = if config.edge_cases && rng.one_in(4) {
+                1
+            } else {
+                rng.range(1, slot * span + span - src)
+            };
+            let dst = rng.below(LIMIT - len);
+            writeln!(out, "{} {} {}", dst, src, len).unwrap();
+        }
+    }
+    out
+}
+
+/// Day 6: `size` races, each with a record that can be beaten.
+///
+/// Part 2 joins all numbers together, so it only works for a handful of races.
+pub fn races(config: &Config) -> String {
+    let mut rng = Rng::new(config.seed);
+    let mut times = Vec::with_capacity(config.size);
+    let mut records = Vec::with_capacity(config.size);
+    for _ in 0..config.size {
+        let time = rng.range(2, 99);
+        let best = (time / 2) * (time - time / 2);
+        let record = if config.edge_cases && rng.one_in(4) {
+            // matching the best possible distance is not enough to win
+            best
+        } else {
+            rng.below(best)
+        };
+        times.push(time);
+        records.push(record);
+    }
+    let mut out = String::from("Time:    ");
+    for (time, record) in times.iter().zip(&records) {
+        let width = record.to_string().len().max(time.to_string().len());
+        write!(out, " {:>width$}", time, width = width).unwrap();
+    }
+    out.push_str("\nDistance:");
+    for (time, record) in times.iter().zip(&records) {
+        let width = record.to_string().len().max(time.to_string().len());
+        write!(out, " {:>width$}", record, width = width).unwrap();
+    }
+    out.push('\n');
+    out
+}
+
+/// Day 7: `size` distinct Camel Cards hands and their bids.
+///
+/// There are only `13^5` distinct hands, so larger sizes are capped.
+pub fn hands(config: &Config) -> String {
+    const LABELS: &[u8; 13] = b"23456789TJQKA";
+    const HANDS: u64 = 13 * 13 * 13 * 13 * 13;
+    let mut rng = Rng::new(config.seed);
+    let size = (config.size as u64).min(HANDS);
+    let mut seen = BTreeSet::new();
+    let mut out = String::with_capacity(size as usize * 10);
+    let mut push = |hand: &[u8], bid: u64, out: &mut String| {
+        if seen.insert(hand.to_vec()) {
+            out.push_str(std::str::from_utf8(hand).unwrap());
+            writeln!(out, " {}", bid).unwrap();
+            true
+        } else {
+            false
+        }
+    };
+    let mut count = 0;
+    if config.edge_cases {
+        for hand in [b"JJJJJ", b"AAAAA", b"2345J", b"JJ2JJ", b"KK677", b"KTJJT"] {
+            if count < size && push(hand, rng.range(1, 1000), &mut out) {
+                count += 1;
+            }
+        }
+    }
+    while count < size {
+        let mut hand = [0u8; 5];
+        if rng.one_in(4) {
+            // repeat cards to get more than just high cards and pairs
+            let card = *rng.pick(LABELS);
+            hand = [card; 5];
+            for c in hand.iter_mut().skip(rng.range(1, 4) as usize) {
+                *c = *rng.pick(LABELS);
+            }
+            for i in (1..5).rev() {
+                hand.swap(i, rng.below(i as u64 + 1) as usize);
+            }
+        } else {
+            hand.iter_mut().for_each(|c| *c = *rng.pick(LABELS));
+        }
+        if push(&hand, rng.range(1, 1000), &mut out) {
+            count += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_config_same_input() {
+        for day in DAYS {
+            for edge_cases in [false, true] {
+                let config = Config {
+                    size: 30,
+                    seed: 42,
+                    edge_cases,
+                };
+                let input = generate(day, &config).unwrap();
+                assert_eq!(
+                    generate(day, &config.clone()).unwrap(),
+                    input,
+                    "day {}",
+                    day
+                );
+                let other = Config { seed: 43, ..config };
+                assert_ne!(generate(day, &other).unwrap(), input, "day {}", day);
+            }
+        }
+        assert_eq!(generate(25, &Config::default()), None);
+    }
+
+    #[test]
+    fn calibration_lines_have_a_digit() {
+        for seed in 0..20 {
+            let config = Config {
+                seed,
+                ..Config::default()
+            };
+            for line in calibration(&config).lines() {
+                assert!(line.bytes().any(|b| b.is_ascii_digit()), "{:?}", line);
+            }
+        }
+    }
+
+    #[test]
+    fn schematic_has_a_gear_with_three_neighbours() {
+        let config = Config {
+            size: 20,
+            edge_cases: true,
+            ..Config::default()
+        };
+        let input = schematic(&config);
+        let rows = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+        assert_eq!(rows[10][10], b'*');
+        assert_eq!([rows[9][10], rows[11][10], rows[10][11]], *b"372");
+    }
+}
@@ -0,0 +1,284 @@
+//! Finding and opening puzzle inputs, which may be compressed to keep large generated inputs
+//! small.
+
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+/// Environment variable naming a directory to look for inputs in.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// The input path given with `--input` on the command line, or `default` without one.
+pub fn arg(default: &str) -> String {
+    std::env::args()
+        .skip_while(|a| a != "--input")
+        .nth(1)
+        .unwrap_or_else(|| default.to_string())
+}
+
+/// The places to look for the input at `path`, in the order they are tried:
+///
+/// 1. `path` itself, relative to the current directory like a path from the command line,
+/// 2. in the directory named by [`INPUT_DIR_VAR`], if it is set,
+/// 3. in the root of the workspace,
+/// 4. in the directory of the crate being run, which cargo sets as `CARGO_MANIFEST_DIR` for
+///    `cargo run` and `cargo test`.
+///
+/// An absolute `path` is only looked for where it points.
+pub fn candidates(path: &Path) -> Vec<PathBuf> {
+    candidates_in(
+        path,
+        std::env::var_os(INPUT_DIR_VAR).map(PathBuf::from),
+        std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from),
+    )
+}
+
+fn candidates_in(
+    path: &Path,
+    input_dir: Option<PathBuf>,
+    crate_dir: Option<PathBuf>,
+) -> Vec<PathBuf> {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent();
+    let dirs = [input_dir.as_deref(), workspace, crate_dir.as_deref()];
+    let mut candidates = vec![path.to_path_buf()];
+    for dir in dirs.into_iter().flatten() {
+        let candidate = dir.join(path);
+        if !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+/// No input was found, with why each of the places it was looked for failed.
+#[derive(Debug)]
+pub struct NotFound {
+    pub path: PathBuf,
+    pub tried: Vec<(PathBuf, io::Error)>,
+}
+
+impl Display for NotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "input {:?} not found, tried:", self.path)?;
+        for (path, e) in &self.tried {
+            write!(f, "\n  {}: {}", path.display(), e)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+/// The first of the [`candidates`] for `path` that is a file.
+pub fn resolve(path: &Path) -> Result<PathBuf, NotFound> {
+    resolve_in(candidates(path), path)
+}
+
+fn resolve_in(candidates: Vec<PathBuf>, path: &Path) -> Result<PathBuf, NotFound> {
+    let mut tried = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        match std::fs::metadata(&candidate) {
+            Ok(meta) if meta.is_file() => return Ok(candidate),
+            Ok(_) => tried.push((candidate, io::Error::other("not a file"))),
+            Err(e) => tried.push((candidate, e)),
+        }
+    }
+    Err(NotFound {
+        path: path.to_path_buf(),
+        tried,
+    })
+}
+
+/// How an input file is compressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+    Xz,
+}
+
+impl Compression {
+    /// The compression implied by the extension of `path`, if it has a known one.
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "gz" => Some(Compression::Gzip),
+            "zst" => Some(Compression::Zstd),
+            "xz" => Some(Compression::Xz),
+            _ => None,
+        }
+    }
+
+    /// The compression of a file starting with `bytes`.
+    pub fn from_magic(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gzip
+        } else if bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Compression::Zstd
+        } else if bytes.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+            Compression::Xz
+        } else {
+            Compression::None
+        }
+    }
+}
+
+impl Display for Compression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Compression::None => write!(f, "uncompressed"),
+            Compression::Gzip => write!(f, "gzip"),
+            Compression::Zstd => write!(f, "zstd"),
+            Compression::Xz => write!(f, "xz"),
+        }
+    }
+}
+
+/// Open the file at `path`, decompressing it if its extension or first bytes say it is
+/// compressed.
+pub fn open(path: &Path) -> io::Result<Box<dyn Read>> {
+    let mut file = BufReader::new(File::open(path)?);
+    let compression = match Compression::from_extension(path) {
+        Some(compression) => compression,
+        None => Compression::from_magic(file.fill_buf()?),
+    };
+    decompress(file, compression)
+}
+
+/// Wrap `reader` to decompress what it reads.
+///
+/// Support for each compression can be left out through the crate features of the same name.
+pub fn decompress(
+    reader: impl BufRead + 'static,
+    compression: Compression,
+) -> io::Result<Box<dyn Read>> {
+    match compression {
+        Compression::None => Ok(Box::new(reader)),
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => Ok(Box::new(flate2::bufread::MultiGzDecoder::new(reader))),
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => ruzstd::decoding::StreamingDecoder::new(reader)
+            .map(|decoder| Box::new(decoder) as Box<dyn Read>)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        #[cfg(feature = "xz")]
+        Compression::Xz => {
+            // lzma-rs cannot decompress incrementally, so the whole input is decompressed at once
+            let mut reader = reader;
+            let mut out = Vec::new();
+            lzma_rs::xz_decompress(&mut reader, &mut out)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", e)))?;
+            Ok(Box::new(io::Cursor::new(out)))
+        }
+        #[allow(unreachable_patterns)]
+        compression => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("built without support for {} input", compression),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "Time:      7  15   30\nDistance:  9  40  200\n";
+
+    fn read(bytes: Vec<u8>, compression: Compression) -> String {
+        assert_eq!(Compression::from_magic(&bytes), compression);
+        let mut out = String::new();
+        decompress(io::Cursor::new(bytes), compression)
+            .unwrap()
+            .read_to_string(&mut out)
+            .unwrap();
+        out
+    }
+
+    #[test]
+    fn uncompressed() {
+        assert_eq!(read(TEXT.into(), Compression::None), TEXT);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn gzip() {
+        use std::io::Write;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        encoder.write_all(TEXT.as_bytes()).unwrap();
+        assert_eq!(read(encoder.finish().unwrap(), Compression::Gzip), TEXT);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd() {
+        let bytes = ruzstd::encoding::compress_to_vec(
+            TEXT.as_bytes(),
+            ruzstd::encoding::CompressionLevel::Fastest,
+        );
+        assert_eq!(read(bytes, Compression::Zstd), TEXT);
+    }
+
+    #[cfg(feature = "xz")]
+    #[test]
+    fn xz() {
+        let mut bytes = Vec::new();
+        lzma_rs::xz_compress(&mut TEXT.as_bytes(), &mut bytes).unwrap();
+        assert_eq!(read(bytes, Compression::Xz), TEXT);
+    }
+
+    #[test]
+    fn resolves_from_the_workspace_root() {
+        // tests run in the directory of this crate
+        let found = resolve(Path::new("adv-2023-day1/Cargo.toml")).unwrap();
+        assert!(found.ends_with("adv-2023-day1/Cargo.toml"));
+        assert!(found.is_file());
+    }
+
+    #[test]
+    fn resolves_from_the_crate_being_run() {
+        let crate_dir = PathBuf::from("/nowhere/adv-2023-day0");
+        let path = Path::new("input/list.txt");
+        let tried = candidates_in(path, None, Some(crate_dir.clone()));
+        assert_eq!(tried.last(), Some(&crate_dir.join(path)));
+        // cargo points to this crate while testing it
+        let tried = candidates(path);
+        let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert_eq!(tried.last(), Some(&crate_dir.join(path)));
+    }
+
+    #[test]
+    fn input_dir_comes_before_the_workspace() {
+        let dir = std::env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("adv-2023-day1")).unwrap();
+        std::fs::write(dir.join("adv-2023-day1/Cargo.toml"), "").unwrap();
+        let path = Path::new("adv-2023-day1/Cargo.toml");
+        let found = resolve_in(candidates_in(path, Some(dir.clone()), None), path);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(found.unwrap(), dir.join(path));
+    }
+
+    #[test]
+    fn not_found_lists_every_path_tried() {
+        let path = Path::new("adv-2023-day0/input/list.txt");
+        let crate_dir = PathBuf::from("/nowhere/adv-2023-day0");
+        let candidates = candidates_in(path, Some(PathBuf::from("/nowhere")), Some(crate_dir));
+        assert_eq!(candidates.len(), 4);
+        let e = resolve_in(candidates.clone(), path).unwrap_err();
+        let message = e.to_string();
+        assert_eq!(message.lines().count(), 5);
+        for candidate in candidates {
+            assert!(message.contains(&candidate.display().to_string()));
+        }
+    }
+
+    #[test]
+    fn extensions() {
+        let ext = |p: &str| Compression::from_extension(Path::new(p));
+        assert_eq!(ext("input/list.txt.gz"), Some(Compression::Gzip));
+        assert_eq!(ext("input/list.txt.zst"), Some(Compression::Zstd));
+        assert_eq!(ext("input/list.txt.xz"), Some(Compression::Xz));
+        assert_eq!(ext("input/list.txt"), None);
+    }
+}
@@ -1,43 +1,104 @@
+pub mod diff;
+pub mod gen;
+pub mod input;
+pub mod json;
+
+pub use adv_2023_derive::Task;
+
 use std::{
-    fmt::Display,
-    fs::File,
-    io::{BufRead, BufReader},
+    fmt::{Debug, Display},
+    io::{BufRead, BufReader, Read},
+    path::Path,
 };
 
-pub struct LineParser {
-    line: String,
-    file: BufReader<File>,
+/// Find the input at `path` and open it, decompressing it if needed, see [`input`].
+fn open(path: &str) -> Box<dyn Read> {
+    let path = input::resolve(Path::new(path)).unwrap_or_else(|e| panic!("{}", e));
+    input::open(&path).unwrap_or_else(|e| panic!("Failed to open file: {:?} ({})", path, e))
+}
+
+/// The byte order mark some editors put at the start of UTF-8 files.
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// Split the first line off `bytes`, returning it and the rest of `bytes`.
+///
+/// A line ends at `\n`, `\r\n`, a `\r` on its own or the end of `bytes`.
+fn split_line(bytes: &[u8]) -> (&[u8], &[u8]) {
+    match bytes.iter().position(|&b| b == b'\n' || b == b'\r') {
+        Some(i) if bytes[i..].starts_with(b"\r\n") => (&bytes[..i], &bytes[i + 2..]),
+        Some(i) => (&bytes[..i], &bytes[i + 1..]),
+        None => (bytes, &[]),
+    }
+}
+
+/// The lines of `bytes` without their line endings, split as [`LineParser`] does.
+pub fn lines(mut bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    std::iter::from_fn(move || {
+        if bytes.is_empty() {
+            return None;
+        }
+        let (line, rest) = split_line(bytes);
+        bytes = rest;
+        Some(line)
+    })
+}
+
+/// Reads lines from a file, or any other buffered reader, without their line endings.
+///
+/// Lines are read as bytes, so only tasks that ask for `&str` lines need valid UTF-8. A byte
+/// order mark at the start is skipped, a stray `\r` is read as a line break of its own and the
+/// last line does not need to end with a line break.
+pub struct LineParser<R = BufReader<Box<dyn Read>>> {
+    line: Vec<u8>,
+    pos: usize,
+    number: usize,
+    reader: R,
 }
 
 impl LineParser {
     pub fn new(path: &str) -> Self {
-        match File::open(path) {
-            Ok(file) => Self {
-                file: BufReader::new(file),
-                line: String::with_capacity(64),
-            },
-            Err(e) => match File::open(format!("../{path}")) {
-                Ok(file) => Self {
-                    file: BufReader::new(file),
-                    line: String::with_capacity(64),
-                },
-                Err(_) => {
-                    panic!("Failed to open file: {:?} ({:?})", path, e);
-                }
-            },
+        Self::from_reader(BufReader::new(open(path)))
+    }
+}
+
+impl<R: BufRead> LineParser<R> {
+    pub fn from_reader(reader: R) -> Self {
+        Self {
+            reader,
+            line: Vec::with_capacity(64),
+            pos: 0,
+            number: 0,
         }
     }
 
-    fn next(&mut self) -> Option<&str> {
-        self.line.clear();
-        let len = self.file.read_line(&mut self.line).unwrap();
-        if len == 0 {
-            return None;
+    /// The next line, or `None` at the end of the input.
+    pub fn next_bytes(&mut self) -> Option<&[u8]> {
+        if self.pos >= self.line.len() {
+            self.line.clear();
+            self.pos = 0;
+            let len = self.reader.read_until(b'\n', &mut self.line).unwrap();
+            if len == 0 {
+                return None;
+            }
+            if self.number == 0 && self.line.starts_with(BOM) {
+                self.pos = BOM.len();
+            }
         }
-        let line = self.line.as_str();
-        let line = line.strip_suffix('\n').unwrap_or(line);
-        let line = line.strip_suffix('\r').unwrap_or(line);
-        Some(line)
+        let rest = &self.line[self.pos..];
+        let (line, tail) = split_line(rest);
+        let start = self.pos;
+        self.pos += rest.len() - tail.len();
+        self.number += 1;
+        Some(&self.line[start..start + line.len()])
+    }
+
+    /// Like [`LineParser::next_bytes`], panicking if the line is not valid UTF-8.
+    fn next(&mut self) -> Option<&str> {
+        let number = self.number + 1;
+        let line = self.next_bytes()?;
+        Some(std::str::from_utf8(line).unwrap_or_else(|e| {
+            panic!("line {} is not valid UTF-8: {}", number, e);
+        }))
     }
 
     pub fn process<S, L, P: Fn(&str) -> L, F: FnMut(&mut S, L)>(
@@ -61,39 +122,278 @@ pub trait Task {
     where
         Self: 'a;
     fn parse<'a>(&self, line: &'a str) -> Self::Input<'a>;
+
+    /// Parse a line that has not been checked to be UTF-8 yet.
+    ///
+    /// By default the line is checked and passed to [`Task::parse`]. Tasks that only look at the
+    /// bytes of a line can skip the check by overriding this.
+    fn parse_bytes<'a>(&self, line: &'a [u8]) -> Self::Input<'a> {
+        match std::str::from_utf8(line) {
+            Ok(line) => self.parse(line),
+            Err(e) => panic!(
+                "line is not valid UTF-8: {} in {:?}",
+                e,
+                String::from_utf8_lossy(line)
+            ),
+        }
+    }
+
     fn process(&mut self, input: Self::Input<'_>);
     fn output(&mut self) -> Self::Output<'_>;
+
+    /// Process every line of the file at `path`, or of stdin if `path` is `-`.
     fn run(&mut self, path: &str) -> Self::Output<'_> {
-        let mut par = LineParser::new(path);
-        while let Some(line) = par.next() {
-            let input = self.parse(line);
+        if path == "-" {
+            return self.run_reader(std::io::stdin().lock());
+        }
+        self.run_reader(open(path))
+    }
+
+    fn run_reader(&mut self, reader: impl Read) -> Self::Output<'_> {
+        let mut par = LineParser::from_reader(BufReader::new(reader));
+        while let Some(line) = par.next_bytes() {
+            let input = self.parse_bytes(line);
             self.process(input);
         }
         self.output()
     }
+
+    fn run_str(&mut self, input: &str) -> Self::Output<'_> {
+        self.run_reader(input.as_bytes())
+    }
 }
 
-pub struct SumTask<I> {
-    pub sum: u64,
-    p: fn(&SumTask<I>, &str) -> I,
-    f: fn(&mut SumTask<I>, num: I),
+/// Chunks of input smaller than this are not worth a thread of their own.
+pub const PARALLEL_MIN_BYTES: usize = 1 << 14;
+
+/// The number of threads worth running at once on this machine.
+pub fn available_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
 }
 
-impl<I> SumTask<I> {
-    pub fn new(p: fn(&SumTask<I>, &str) -> I, f: fn(&mut SumTask<I>, num: I)) -> Self {
-        Self { sum: 0, p, f }
+/// A task where every line can be processed on its own, so chunks of the input can be processed
+/// on separate threads and combined afterwards.
+pub trait Split: Task + Send + Sized {
+    /// A task with the same configuration that has not processed anything yet, for the chunk of
+    /// the input starting at the 1-based `line`.
+    fn split(&self, line: usize) -> Self;
+
+    /// Add what `other` processed, which is the chunk of input following what was merged before.
+    fn merge(&mut self, other: Self);
+
+    /// Like [`Task::run`], but processes the input in chunks on up to `threads` threads.
+    fn run_parallel(&mut self, path: &str, threads: usize) -> Self::Output<'_> {
+        let mut input = Vec::new();
+        if path == "-" {
+            std::io::stdin().read_to_end(&mut input).unwrap();
+        } else {
+            open(path).read_to_end(&mut input).unwrap();
+        }
+        self.run_parallel_bytes(&input, threads)
+    }
+
+    /// Like [`Split::run_parallel`], for input that is already in memory.
+    fn run_parallel_str(&mut self, input: &str, threads: usize) -> Self::Output<'_> {
+        self.run_parallel_bytes(input.as_bytes(), threads)
+    }
+
+    /// Like [`Split::run_parallel`], for input that is already in memory but may not be UTF-8.
+    ///
+    /// If chunks panic, the panic of the first one is passed on.
+    fn run_parallel_bytes(&mut self, input: &[u8], threads: usize) -> Self::Output<'_> {
+        let input = input.strip_prefix(BOM).unwrap_or(input);
+        let parts = std::thread::scope(|s| {
+            let handles = chunks(input, threads)
+                .into_iter()
+                .map(|(line, chunk)| {
+                    let mut task = self.split(line);
+                    s.spawn(move || {
+                        for line in lines(chunk) {
+                            let input = task.parse_bytes(line);
+                            task.process(input);
+                        }
+                        task
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+                .collect::<Vec<_>>()
+        });
+        for part in parts {
+            self.merge(part);
+        }
+        self.output()
+    }
+}
+
+/// Split `input` into at most `count` chunks of whole lines, each with its 1-based first line.
+fn chunks(input: &[u8], count: usize) -> Vec<(usize, &[u8])> {
+    let count = count.min(input.len() / PARALLEL_MIN_BYTES).max(1);
+    let size = input.len().div_ceil(count).max(1);
+    let mut chunks = Vec::with_capacity(count);
+    let mut line = 1;
+    let mut rest = input;
+    while !rest.is_empty() {
+        let end = match rest
+            .get(size..)
+            .and_then(|tail| tail.iter().position(|&b| b == b'\n'))
+        {
+            Some(pos) => size + pos + 1,
+            None => rest.len(),
+        };
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push((line, chunk));
+        line += lines(chunk).count();
+        rest = tail;
+    }
+    chunks
+}
+
+/// A number that lines can be folded into.
+pub trait Accumulator: Copy + Ord + Display + Debug {
+    const ZERO: Self;
+    const ONE: Self;
+    const MIN: Self;
+    const MAX: Self;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_accumulator {
+    ($($t:ty),*) => {
+        $(
+            impl Accumulator for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_accumulator!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// How the values of a [`FoldTask`] are combined.
+#[derive(Debug, Clone, Copy)]
+pub enum Fold<A> {
+    Sum,
+    Product,
+    /// The smallest value, or `A::MAX` if there are none.
+    Min,
+    /// The largest value, or `A::MIN` if there are none.
+    Max,
+    /// The number of values, regardless of what they are.
+    Count,
+    /// Start from `init` and combine with `combine`, which returns `None` on overflow.
+    ///
+    /// For [`Split`] to give the same result as [`Task::run`], `combine` must be associative and
+    /// `init` must be its identity, since every chunk of the input starts again from `init`.
+    Custom {
+        init: A,
+        combine: fn(A, A) -> Option<A>,
+    },
+}
+
+impl<A: Accumulator> Fold<A> {
+    /// The result of folding no values at all.
+    pub fn init(&self) -> A {
+        match self {
+            Fold::Sum | Fold::Count => A::ZERO,
+            Fold::Product => A::ONE,
+            Fold::Min => A::MAX,
+            Fold::Max => A::MIN,
+            Fold::Custom { init, .. } => *init,
+        }
     }
+
+    /// Combine two results, or `None` if it overflows.
+    ///
+    /// For [`Fold::Count`] both are counts, so single values need to be counted as `A::ONE`.
+    pub fn combine(&self, acc: A, value: A) -> Option<A> {
+        match self {
+            Fold::Sum | Fold::Count => acc.checked_add(value),
+            Fold::Product => acc.checked_mul(value),
+            Fold::Min => Some(acc.min(value)),
+            Fold::Max => Some(acc.max(value)),
+            Fold::Custom { combine, .. } => combine(acc, value),
+        }
+    }
+
+    /// Fold all `values`, or `None` if it overflows.
+    pub fn fold(&self, values: impl IntoIterator<Item = A>) -> Option<A> {
+        values
+            .into_iter()
+            .try_fold(self.init(), |acc, value| match self {
+                Fold::Count => self.combine(acc, A::ONE),
+                _ => self.combine(acc, value),
+            })
+    }
+}
+
+/// A task that folds a value from every line into a single result.
+pub struct FoldTask<I, A = u64> {
+    pub acc: A,
+    pub fold: Fold<A>,
+    p: fn(&FoldTask<I, A>, &str) -> I,
+    f: fn(&mut FoldTask<I, A>, num: I),
 }
 
-impl<I: Into<u64>> SumTask<I> {
+/// A task that sums a value from every line.
+pub type SumTask<I> = FoldTask<I, u64>;
+
+impl<I, A: Accumulator> FoldTask<I, A> {
+    /// A task summing what `f` pushes for every line parsed by `p`.
+    pub fn new(p: fn(&FoldTask<I, A>, &str) -> I, f: fn(&mut FoldTask<I, A>, num: I)) -> Self {
+        Self::with_fold(Fold::Sum, p, f)
+    }
+
+    pub fn with_fold(
+        fold: Fold<A>,
+        p: fn(&FoldTask<I, A>, &str) -> I,
+        f: fn(&mut FoldTask<I, A>, num: I),
+    ) -> Self {
+        Self {
+            acc: fold.init(),
+            fold,
+            p,
+            f,
+        }
+    }
+
+    /// Fold `value` into the result, panicking if it overflows.
+    pub fn push(&mut self, value: A) {
+        let value = if matches!(self.fold, Fold::Count) {
+            A::ONE
+        } else {
+            value
+        };
+        self.acc = self
+            .fold
+            .combine(self.acc, value)
+            .unwrap_or_else(|| panic!("{:?} overflowed at {} with {}", self.fold, self.acc, value));
+    }
+}
+
+impl<I: Into<A>, A: Accumulator> FoldTask<I, A> {
     pub fn add(slf: &mut Self, num: I) {
-        slf.sum += num.into();
+        slf.push(num.into());
     }
 }
 
-impl<I: 'static> Task for SumTask<I> {
+impl<I: 'static, A: Accumulator + 'static> Task for FoldTask<I, A> {
     type Input<'a> = I;
-    type Output<'a> = u64;
+    type Output<'a> = A;
 
     fn parse<'a>(&self, line: &'a str) -> Self::Input<'a> {
         (self.p)(self, line)
@@ -104,6 +404,219 @@ impl<I: 'static> Task for SumTask<I> {
     }
 
     fn output(&mut self) -> Self::Output<'_> {
-        self.sum
+        self.acc
+    }
+}
+
+impl<I: 'static, A: Accumulator + Send + 'static> Split for FoldTask<I, A> {
+    fn split(&self, _line: usize) -> Self {
+        Self::with_fold(self.fold, self.p, self.f)
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.acc = self.fold.combine(self.acc, other.acc).unwrap_or_else(|| {
+            panic!(
+                "{:?} overflowed merging {} and {}",
+                self.fold, self.acc, other.acc
+            )
+        });
+    }
+}
+
+/// An error in the input, with the 1-based line and column it was found at.
+///
+/// `kind` says what went wrong and is up to each day. The line is left for the caller to fill
+/// in with [`ParseError::at_line`], as a single line does not know where it is.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError<K> {
+    pub line: Option<usize>,
+    pub column: usize,
+    pub kind: K,
+}
+
+impl<K> ParseError<K> {
+    pub fn new(column: usize, kind: K) -> Self {
+        Self {
+            line: None,
+            column,
+            kind,
+        }
+    }
+
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+}
+
+impl<K: Display> Display for ParseError<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}, column {}: {}", line, self.column, self.kind)
+        } else {
+            write!(f, "column {}: {}", self.column, self.kind)
+        }
+    }
+}
+
+impl<K: Debug + Display> std::error::Error for ParseError<K> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_str_strips_line_endings() {
+        let mut task = SumTask::new(|_, l| l.parse::<u64>().unwrap(), SumTask::add);
+        assert_eq!(task.run_str("1\n20\r\n300"), 321);
+        let mut task = SumTask::new(|_, l| l.parse::<u64>().unwrap(), SumTask::add);
+        assert_eq!(task.run_reader("4\n5\n".as_bytes()), 9);
+    }
+
+    #[test]
+    fn line_parser_reads_bytes() {
+        let input: &[u8] = b"\xef\xbb\xbfone\r\ntwo\rthree\n\xff\n\nlast";
+        let mut par = LineParser::from_reader(input);
+        let mut read = Vec::new();
+        while let Some(line) = par.next_bytes() {
+            read.push(line.to_vec());
+        }
+        let expected: [&[u8]; 6] = [b"one", b"two", b"three", b"\xff", b"", b"last"];
+        assert_eq!(read, expected);
+        assert_eq!(lines(&input[3..]).collect::<Vec<_>>(), expected);
+    }
+
+    /// Counts the non-ASCII bytes of every line, without requiring UTF-8.
+    struct NonAscii(FoldTask<u64>);
+
+    impl Task for NonAscii {
+        type Input<'a> = u64;
+        type Output<'a> = u64;
+
+        fn parse<'a>(&self, line: &'a str) -> Self::Input<'a> {
+            self.parse_bytes(line.as_bytes())
+        }
+
+        fn parse_bytes<'a>(&self, line: &'a [u8]) -> Self::Input<'a> {
+            line.iter().filter(|b| !b.is_ascii()).count() as u64
+        }
+
+        fn process(&mut self, input: Self::Input<'_>) {
+            self.0.push(input);
+        }
+
+        fn output(&mut self) -> Self::Output<'_> {
+            self.0.output()
+        }
+    }
+
+    impl Split for NonAscii {
+        fn split(&self, line: usize) -> Self {
+            NonAscii(self.0.split(line))
+        }
+
+        fn merge(&mut self, other: Self) {
+            self.0.merge(other.0);
+        }
+    }
+
+    #[test]
+    fn parse_bytes_skips_utf8_validation() {
+        let task = || NonAscii(SumTask::new(|_, _| 0, SumTask::add));
+        let input = [BOM, &b"a\xff\rb\r\n\xfe\xfd\n".repeat(10_000)].concat();
+        assert_eq!(task().run_reader(input.as_slice()), 30_000);
+        assert_eq!(task().run_parallel_bytes(&input, 4), 30_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "not valid UTF-8")]
+    fn parse_checks_utf8() {
+        let mut task = SumTask::new(|_, l| l.len() as u64, SumTask::add);
+        task.run_reader(b"ok\n\xff\n".as_slice());
+    }
+
+    #[test]
+    fn chunks_end_on_line_breaks() {
+        let input = (1..=20_000).map(|n| format!("{}\n", n)).collect::<String>();
+        let chunks = chunks(input.as_bytes(), 3);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(
+            chunks.iter().map(|c| c.1).collect::<Vec<_>>().concat(),
+            input.as_bytes()
+        );
+        for (line, chunk) in chunks {
+            assert!(chunk.ends_with(b"\n"));
+            assert_eq!(lines(chunk).next(), Some(line.to_string().as_bytes()));
+        }
+    }
+
+    #[test]
+    fn folds_check_for_overflow() {
+        assert_eq!(Fold::Sum.fold([1u8, 2, 3]), Some(6));
+        assert_eq!(Fold::Product.fold([2u8, 3, 4]), Some(24));
+        assert_eq!(Fold::Min.fold([5u8, 2, 9]), Some(2));
+        assert_eq!(Fold::Max.fold([5u8, 2, 9]), Some(9));
+        assert_eq!(Fold::Count.fold([5u8, 2, 9]), Some(3));
+        assert_eq!(Fold::<u8>::Min.fold([]), Some(u8::MAX));
+        assert_eq!(Fold::Sum.fold([200u8, 100]), None);
+        assert_eq!(Fold::Product.fold([16u8, 16]), None);
+        assert_eq!(GCD.fold([12, 18, 30]), Some(6));
+    }
+
+    /// A custom fold with `0` as the identity of its `combine`.
+    const GCD: Fold<u64> = Fold::Custom {
+        init: 0,
+        combine: |mut a, mut b| {
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            Some(a)
+        },
+    };
+
+    #[test]
+    fn parallel_fold_matches_sequential() {
+        let input = (1..=20_000).map(|n| format!("{}\n", n)).collect::<String>();
+        for fold in [Fold::<u64>::Sum, Fold::Min, Fold::Max, Fold::Count] {
+            let mut task =
+                FoldTask::with_fold(fold, |_, l| l.parse::<u64>().unwrap(), FoldTask::add);
+            let expected = fold.fold(1..=20_000).unwrap();
+            assert_eq!(task.run_parallel_str(&input, 4), expected);
+        }
+        let input = (1..=20_000)
+            .map(|n| format!("{}\n", n * 6))
+            .collect::<String>();
+        let task = || FoldTask::with_fold(GCD, |_, l| l.parse::<u64>().unwrap(), FoldTask::add);
+        assert_eq!(task().run_str(&input), 6);
+        assert_eq!(task().run_parallel_str(&input, 4), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "Product overflowed")]
+    fn overflow_panics() {
+        let mut task = FoldTask::with_fold(
+            Fold::<u8>::Product,
+            |_, l| l.parse::<u8>().unwrap(),
+            FoldTask::add,
+        );
+        task.run_parallel_str("16\n16\n", 1);
+    }
+
+    #[test]
+    fn parallel_sum_matches_sequential() {
+        let input = (1..=20_000).map(|n| format!("{}\n", n)).collect::<String>();
+        let mut task = SumTask::new(|_, l| l.parse::<u64>().unwrap(), SumTask::add);
+        assert_eq!(task.run_parallel_str(&input, 4), 20_000 * 20_001 / 2);
+        let mut task = SumTask::new(|_, l| l.parse::<u64>().unwrap(), SumTask::add);
+        assert_eq!(task.run_parallel_str("1\n2\n3", 4), 6);
+    }
+
+    #[test]
+    fn parse_errors_show_where_they_are() {
+        let e = ParseError::new(4, "invalid card");
+        assert_eq!(e.to_string(), "column 4: invalid card");
+        let e = e.at_line(2);
+        assert_eq!(e.line, Some(2));
+        assert_eq!(e.to_string(), "line 2, column 4: invalid card");
     }
 }
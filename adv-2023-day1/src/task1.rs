@@ -0,0 +1,41 @@
+use adv_2023_common::{available_threads, input, Split, SumTask, Task};
+
+struct ParsedLine {
+    pub fst: u64,
+    pub lst: u64,
+}
+
+impl ParsedLine {
+    pub fn parse(line: &str) -> Self {
+        let mut found = false;
+        let mut fst = 0;
+        let mut lst = 0;
+        for c in line.bytes() {
+            if c.is_ascii_digit() {
+                if !found {
+                    fst = (c - b'0') as u64 * 10;
+                    found = true;
+                }
+                lst = c - b'0';
+            }
+        }
+        Self {
+            fst,
+            lst: lst as u64,
+        }
+    }
+}
+
+fn state() -> SumTask<ParsedLine> {
+    SumTask::new(|_, i| ParsedLine::parse(i), |s, l| s.push(l.fst + l.lst))
+}
+
+/// Solve on the calling thread, leaving it to the caller how many inputs to solve at once.
+pub fn solve(path: &str) -> String {
+    state().run(path).to_string()
+}
+
+pub fn main() {
+    let path = input::arg("adv-2023-day1/input/list.txt");
+    println!("{}", state().run_parallel(&path, available_threads()));
+}
@@ -0,0 +1,384 @@
+pub mod task1;
+pub mod task2;
+
+use std::{collections::BTreeSet, fmt::Display};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    MissingPrefix,
+    InvalidId,
+    MissingColon,
+    InvalidPull(String),
+    InvalidCount(String),
+    UnknownColour(String),
+    DuplicateId(u64),
+    NonSequentialId { expected: u64, found: u64 },
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorKind::MissingPrefix => write!(f, "expected line to start with \"Game\""),
+            ErrorKind::InvalidId => write!(f, "expected a numeric game id"),
+            ErrorKind::MissingColon => write!(f, "expected ':' after game id"),
+            ErrorKind::InvalidPull(pull) => write!(f, "invalid pull: {:?}", pull),
+            ErrorKind::InvalidCount(count) => write!(f, "invalid count: {:?}", count),
+            ErrorKind::UnknownColour(colour) => write!(f, "unknown colour: {:?}", colour),
+            ErrorKind::DuplicateId(id) => write!(f, "duplicate game id: {}", id),
+            ErrorKind::NonSequentialId { expected, found } => {
+                write!(f, "expected game id {} but found {}", expected, found)
+            }
+        }
+    }
+}
+
+/// An error in a game, whose line is only known once it has passed through [`Games::record`].
+pub type ParseError = adv_2023_common::ParseError<ErrorKind>;
+
+/// The set of colours a game is played with.
+///
+/// A strict palette only knows the colours it was created with, while a discovering palette
+/// learns every colour it is asked about.
+#[derive(Debug, Default, Clone)]
+pub struct Palette {
+    names: Vec<String>,
+    strict: bool,
+}
+
+impl Palette {
+    pub fn discover() -> Self {
+        Self::default()
+    }
+
+    pub fn strict<S: Into<String>>(names: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            names: names.into_iter().map(Into::into).collect(),
+            strict: true,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Index of `name` in the palette, adding it first if the palette is not strict.
+    pub fn index(&mut self, name: &str) -> Result<usize, ErrorKind> {
+        if let Some(pos) = self.names.iter().position(|n| n == name) {
+            return Ok(pos);
+        }
+        if self.strict {
+            return Err(ErrorKind::UnknownColour(name.to_string()));
+        }
+        self.names.push(name.to_string());
+        Ok(self.names.len() - 1)
+    }
+}
+
+pub struct Game<'a> {
+    pub id: u64,
+    pub pulls: Vec<Vec<(u64, &'a str)>>,
+    line: &'a str,
+    id_column: usize,
+}
+
+impl<'a> Game<'a> {
+    /// Parse a line such as `Game 12: 3 red, 4 blue; 1 green`.
+    ///
+    /// Any amount of whitespace is accepted around the id, the separators and the counts.
+    pub fn parse(line: &'a str) -> Result<Self, ParseError> {
+        let bytes = line.as_bytes();
+        let mut pos = skip_whitespace(bytes, 0);
+        if !bytes[pos..].starts_with(b"Game") {
+            return Err(ParseError::new(pos + 1, ErrorKind::MissingPrefix));
+        }
+        pos = skip_whitespace(bytes, pos + 4);
+        let start = pos;
+        while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        let id = line[start..pos]
+            .parse::<u64>()
+            .map_err(|_| ParseError::new(start + 1, ErrorKind::InvalidId))?;
+        pos = skip_whitespace(bytes, pos);
+        if bytes.get(pos) != Some(&b':') {
+            return Err(ParseError::new(pos + 1, ErrorKind::MissingColon));
+        }
+        let rest = &line[pos + 1..];
+        let mut pulls = Vec::with_capacity(8);
+        // a game without any pulls is valid, but `split` would yield one empty pull for it
+        let rest = if rest.trim().is_empty() { "" } else { rest };
+        for pull in rest.split_terminator(';') {
+            let mut items = Vec::with_capacity(4);
+            for pull_item in pull.split(',') {
+                let pull_item = pull_item.trim();
+                let column = column(line, pull_item);
+                let (n, colour) = pull_item
+                    .split_once(|c: char| c.is_ascii_whitespace())
+                    .ok_or_else(|| {
+                        ParseError::new(column, ErrorKind::InvalidPull(pull_item.to_string()))
+                    })?;
+                let n = n
+                    .parse::<u64>()
+                    .map_err(|_| ParseError::new(column, ErrorKind::InvalidCount(n.to_string())))?;
+                items.push((n, colour.trim()));
+            }
+            pulls.push(items);
+        }
+        Ok(Game {
+            id,
+            pulls,
+            line,
+            id_column: start + 1,
+        })
+    }
+
+    /// The highest count of each colour in a single pull, indexed as in `palette`.
+    ///
+    /// Colours the game never uses are `0`, and the result may be shorter than the palette if
+    /// the palette learns more colours later on.
+    pub fn maxima(&self, palette: &mut Palette) -> Result<Vec<u64>, ParseError> {
+        let mut maxima = vec![0u64; palette.len()];
+        let mut counts = vec![0u64; palette.len()];
+        for pull in &self.pulls {
+            counts.iter_mut().for_each(|c| *c = 0);
+            for &(n, colour) in pull {
+                let idx = palette
+                    .index(colour)
+                    .map_err(|kind| ParseError::new(column(self.line, colour), kind))?;
+                if idx >= counts.len() {
+                    counts.resize(idx + 1, 0);
+                    maxima.resize(idx + 1, 0);
+                }
+                counts[idx] += n;
+            }
+            for (max, &count) in maxima.iter_mut().zip(&counts) {
+                if count > *max {
+                    *max = count;
+                }
+            }
+        }
+        Ok(maxima)
+    }
+}
+
+fn skip_whitespace(bytes: &[u8], mut pos: usize) -> usize {
+    while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+        pos += 1;
+    }
+    pos
+}
+
+/// 1-based column of `part`, which must be a slice of `line`.
+fn column(line: &str, part: &str) -> usize {
+    part.as_ptr() as usize - line.as_ptr() as usize + 1
+}
+
+/// Validates the games of an input in order and resolves their colours.
+pub struct Games {
+    pub palette: Palette,
+    line: usize,
+    last: Option<u64>,
+    seen: BTreeSet<u64>,
+}
+
+impl Games {
+    pub fn new(palette: Palette) -> Self {
+        Self {
+            palette,
+            line: 0,
+            last: None,
+            seen: BTreeSet::new(),
+        }
+    }
+
+    /// Like [`Games::new`], for a chunk of the input starting at the 1-based `line`.
+    ///
+    /// As ids follow the line numbers, the first game of the chunk must have the id `line`. A
+    /// duplicate of an id from an earlier chunk is reported as being out of sequence.
+    pub fn starting_at(palette: Palette, line: usize) -> Self {
+        Self {
+            palette,
+            line: line - 1,
+            last: Some(line as u64 - 1).filter(|&last| last > 0),
+            seen: BTreeSet::new(),
+        }
+    }
+
+    /// Check the next game of the input and return its id and colour maxima.
+    ///
+    /// Game ids must be unique and follow each other, starting at 1.
+    pub fn record(
+        &mut self,
+        game: Result<Game<'_>, ParseError>,
+    ) -> Result<(u64, Vec<u64>), ParseError> {
+        self.line += 1;
+        let line = self.line;
+        let at_line = |e: ParseError| e.at_line(line);
+        let game = game.map_err(at_line)?;
+        let column = game.id_column;
+        if !self.seen.insert(game.id) {
+            let kind = ErrorKind::DuplicateId(game.id);
+            return Err(at_line(ParseError::new(column, kind)));
+        }
+        let expected = self.last.map_or(1, |last| last + 1);
+        if game.id != expected {
+            let kind = ErrorKind::NonSequentialId {
+                expected,
+                found: game.id,
+            };
+            return Err(at_line(ParseError::new(column, kind)));
+        }
+        self.last = Some(game.id);
+        let maxima = game.maxima(&mut self.palette).map_err(at_line)?;
+        Ok((game.id, maxima))
+    }
+}
+
+/// Whether a game with the given maxima could have been played with at most `limits` cubes.
+///
+/// Colours without a limit are treated as not being in the bag at all.
+pub fn possible(maxima: &[u64], limits: &[u64]) -> bool {
+    maxima
+        .iter()
+        .enumerate()
+        .all(|(i, &max)| max <= limits.get(i).copied().unwrap_or(0))
+}
+
+/// The power of the smallest set of cubes, using every colour of a palette with `colours` entries.
+pub fn power(maxima: &[u64], colours: usize) -> u64 {
+    (0..colours)
+        .map(|i| maxima.get(i).copied().unwrap_or(0))
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgb() -> Palette {
+        Palette::strict(["red", "green", "blue"])
+    }
+
+    #[test]
+    fn strict_palette_rejects_unknown_colours() {
+        let game = Game::parse("Game 1: 3 red, 2 purple; 1 blue").unwrap();
+        let e = game.maxima(&mut rgb()).unwrap_err();
+        assert_eq!(e.kind, ErrorKind::UnknownColour("purple".to_string()));
+        assert_eq!(e.column, 18);
+        assert_eq!(e.line, None);
+    }
+
+    #[test]
+    fn discovering_palette_learns_colours() {
+        let mut palette = Palette::discover();
+        let first = Game::parse("Game 1: 3 red, 2 green; 1 blue").unwrap();
+        assert_eq!(first.maxima(&mut palette).unwrap(), [3, 2, 1]);
+        let second = Game::parse("Game 2: 4 purple, 1 red; 2 purple").unwrap();
+        assert_eq!(second.maxima(&mut palette).unwrap(), [1, 0, 0, 4]);
+        assert_eq!(
+            palette.names().collect::<Vec<_>>(),
+            ["red", "green", "blue", "purple"]
+        );
+        // the first game was played before purple was known, so it never used it
+        assert_eq!(power(&[3, 2, 1], palette.len()), 0);
+        assert_eq!(power(&[3, 2, 1], 3), 6);
+    }
+
+    #[test]
+    fn possible_within_limits() {
+        let limits = [12, 13, 14];
+        assert!(possible(&[12, 13, 14], &limits));
+        assert!(possible(&[1], &limits));
+        assert!(!possible(&[13, 0, 0], &limits));
+        // a colour without a limit is not in the bag
+        assert!(possible(&[1, 1, 1, 0], &limits));
+        assert!(!possible(&[1, 1, 1, 1], &limits));
+    }
+
+    fn error(line: &str) -> (usize, ErrorKind) {
+        match Game::parse(line) {
+            Ok(_) => panic!("{:?} should not parse", line),
+            Err(e) => (e.column, e.kind),
+        }
+    }
+
+    #[test]
+    fn parse_tolerates_whitespace() {
+        let game = Game::parse("  Game   3 :  1 red ,2 blue;4  green  ").unwrap();
+        assert_eq!(game.id, 3);
+        assert_eq!(
+            game.pulls,
+            [vec![(1, "red"), (2, "blue")], vec![(4, "green")]]
+        );
+        for line in ["Game 4:", "Game 4:   "] {
+            assert!(Game::parse(line).unwrap().pulls.is_empty());
+        }
+    }
+
+    #[test]
+    fn parse_errors_have_columns() {
+        assert_eq!(error("3: 1 red"), (1, ErrorKind::MissingPrefix));
+        assert_eq!(error("  Round 3: 1 red"), (3, ErrorKind::MissingPrefix));
+        assert_eq!(error("Game x: 1 red"), (6, ErrorKind::InvalidId));
+        assert_eq!(error("Game 3 1 red"), (8, ErrorKind::MissingColon));
+        assert_eq!(error("Game 3"), (7, ErrorKind::MissingColon));
+        assert_eq!(
+            error("Game 1: 3 red, x blue"),
+            (16, ErrorKind::InvalidCount("x".to_string()))
+        );
+        assert_eq!(
+            error("Game 1: red"),
+            (9, ErrorKind::InvalidPull("red".to_string()))
+        );
+    }
+
+    #[test]
+    fn record_checks_ids() {
+        let record = |lines: &[&str]| {
+            let mut games = Games::new(rgb());
+            lines
+                .iter()
+                .map(|line| games.record(Game::parse(line)).map(|(id, _)| id))
+                .collect::<Vec<_>>()
+        };
+        let at = |line, column, kind| ParseError {
+            line: Some(line),
+            column,
+            kind,
+        };
+        assert_eq!(record(&["Game 1: 1 red", "Game 2: 2 blue"]), [Ok(1), Ok(2)]);
+        assert_eq!(
+            record(&["Game 1: 1 red", "Game 1: 2 blue"]),
+            [Ok(1), Err(at(2, 6, ErrorKind::DuplicateId(1)))]
+        );
+        let non_sequential = |expected, found| ErrorKind::NonSequentialId { expected, found };
+        assert_eq!(
+            record(&["Game 1: 1 red", "Game  3: 2 blue"]),
+            [Ok(1), Err(at(2, 7, non_sequential(2, 3)))]
+        );
+        assert_eq!(
+            record(&["Game 2: 1 red"]),
+            [Err(at(1, 6, non_sequential(1, 2)))]
+        );
+        // errors from parsing get the line they were found on too
+        assert_eq!(
+            record(&["Game 1: 1 red", "Game 2: 1 red, 2 purple"]),
+            [
+                Ok(1),
+                Err(at(2, 18, ErrorKind::UnknownColour("purple".to_string())))
+            ]
+        );
+        assert_eq!(
+            record(&["Game 1: 1 red", "Game 2 1 red"]),
+            [Ok(1), Err(at(2, 8, ErrorKind::MissingColon))]
+        );
+    }
+}
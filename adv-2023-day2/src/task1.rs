@@ -0,0 +1,105 @@
+use crate::{possible, Game, Games, Palette, ParseError};
+use adv_2023_common::{available_threads, input, Split, Task};
+
+#[derive(Task)]
+#[task(input = Result<Game<'a>, ParseError>, parse = Game::parse, process = Self::record)]
+struct State {
+    games: Games,
+    limits: Vec<u64>,
+    #[task(output)]
+    sum: u64,
+}
+
+impl State {
+    fn record(&mut self, game: Result<Game<'_>, ParseError>) {
+        let (id, maxima) = self
+            .games
+            .record(game)
+            .unwrap_or_else(|e| panic!("Invalid game: {}", e));
+        if possible(&maxima, &self.limits) {
+            self.sum += id;
+        }
+    }
+}
+
+impl Split for State {
+    fn split(&self, line: usize) -> Self {
+        Self {
+            games: Games::starting_at(self.games.palette.clone(), line),
+            limits: self.limits.clone(),
+            sum: 0,
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.sum += other.sum;
+    }
+}
+
+fn state() -> State {
+    State {
+        games: Games::new(Palette::strict(RGB_FILTER.iter().map(|(name, _)| *name))),
+        limits: RGB_FILTER.iter().map(|(_, limit)| *limit).collect(),
+        sum: 0,
+    }
+}
+
+/// Solve on the calling thread, leaving it to the caller how many inputs to solve at once.
+pub fn solve(path: &str) -> String {
+    state().run(path).to_string()
+}
+
+pub fn main() {
+    let path = input::arg("adv-2023-day2/input/list.txt");
+    println!("{}", state().run_parallel(&path, available_threads()));
+}
+
+static RGB_FILTER: [(&str, u64); 3] = [("red", 12), ("green", 13), ("blue", 14)];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adv_2023_common::{
+        diff::assert_agree,
+        gen::{self, Config, Rng},
+    };
+
+    fn state() -> State {
+        State {
+            games: Games::new(Palette::strict(RGB_FILTER.iter().map(|(name, _)| *name))),
+            limits: RGB_FILTER.iter().map(|(_, limit)| *limit).collect(),
+            sum: 0,
+        }
+    }
+
+    /// Generated games, with an unknown colour on one of the lines for edge cases.
+    fn games(config: &Config) -> String {
+        let input = gen::games(config);
+        if !config.edge_cases {
+            return input;
+        }
+        let mut rng = Rng::new(config.seed);
+        let broken = rng.below(config.size as u64) as usize;
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| match i == broken {
+                true => format!("{}, 1 purple\n", line),
+                false => format!("{}\n", line),
+            })
+            .collect()
+    }
+
+    fn sequential(input: &str) -> u64 {
+        state().run_str(input)
+    }
+
+    fn parallel(input: &str) -> u64 {
+        state().run_parallel_str(input, 4)
+    }
+
+    #[test]
+    fn parallel_matches_sequential() {
+        assert_agree(games, 3000, 4, sequential, parallel);
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{power, Game, Games, Palette, ParseError};
+use adv_2023_common::{input, Task};
+
+struct State {
+    games: Games,
+    maxima: Vec<Vec<u64>>,
+}
+
+impl Task for State {
+    type Input<'a> = Result<Game<'a>, ParseError> where Self: 'a;
+
+    type Output<'a> = u64 where Self: 'a;
+
+    fn parse<'a>(&self, line: &'a str) -> Self::Input<'a> {
+        Game::parse(line)
+    }
+
+    fn process(&mut self, input: Self::Input<'_>) {
+        let (_, maxima) = self
+            .games
+            .record(input)
+            .unwrap_or_else(|e| panic!("Invalid game: {}", e));
+        self.maxima.push(maxima);
+    }
+
+    fn output(&mut self) -> Self::Output<'_> {
+        // the power can only be computed once every colour in the input is known
+        let colours = self.games.palette.len();
+        self.maxima.iter().map(|m| power(m, colours)).sum()
+    }
+}
+
+pub fn solve(path: &str) -> String {
+    let mut state = State {
+        games: Games::new(Palette::discover()),
+        maxima: Vec::new(),
+    };
+    state.run(path).to_string()
+}
+
+pub fn main() {
+    println!("{}", solve(&input::arg("adv-2023-day2/input/list.txt")));
+}
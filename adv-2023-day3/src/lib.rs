@@ -0,0 +1,363 @@
+pub mod task1;
+pub mod task2;
+
+use std::{fmt::Display, ops::Range, str::FromStr};
+
+use adv_2023_common::Task;
+
+/// A number in the schematic, spanning the columns `start..=end` of `row`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Number {
+    pub val: u64,
+    pub row: u32,
+    pub start: u32,
+    pub end: u32,
+    /// Indices of the adjacent symbols in [`Schematic::symbols`].
+    pub symbols: Vec<usize>,
+}
+
+/// Any character in the schematic that is neither a digit nor a `.`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Symbol {
+    pub kind: u8,
+    pub row: u32,
+    pub col: u32,
+    /// Indices of the adjacent numbers in [`Schematic::numbers`].
+    pub numbers: Vec<usize>,
+}
+
+impl Symbol {
+    fn touches(&self, num: &Number) -> bool {
+        self.col + 1 >= num.start && self.col <= num.end + 1
+    }
+}
+
+/// The whole engine schematic, with the adjacency between every number and symbol.
+///
+/// Rows are added one at a time, either through [`Schematic::push_row`] or by running it as a
+/// [`Task`], which outputs the sum of all part numbers.
+#[derive(Debug, Default)]
+pub struct Schematic {
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+    rows: u32,
+    last_numbers: Range<usize>,
+    last_symbols: Range<usize>,
+}
+
+impl Schematic {
+    pub fn parse(input: &str) -> Self {
+        let mut schematic = Self::default();
+        for line in input.lines() {
+            schematic.push_row(line.as_bytes());
+        }
+        schematic
+    }
+
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    pub fn push_row(&mut self, line: &[u8]) {
+        let row = self.rows;
+        self.rows += 1;
+        let numbers = self.numbers.len()..self.numbers.len() + self.parse_numbers(row, line);
+        let symbols = self.symbols.len()..self.symbols.len() + self.parse_symbols(row, line);
+
+        for sym in symbols.clone() {
+            for num in self.last_numbers.clone().chain(numbers.clone()) {
+                self.link(num, sym);
+            }
+        }
+        for sym in self.last_symbols.clone() {
+            for num in numbers.clone() {
+                self.link(num, sym);
+            }
+        }
+
+        self.last_numbers = numbers;
+        self.last_symbols = symbols;
+    }
+
+    fn parse_numbers(&mut self, row: u32, line: &[u8]) -> usize {
+        let before = self.numbers.len();
+        let mut i = 0;
+        while i < line.len() {
+            if !line[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            let mut val = 0;
+            while i < line.len() && line[i].is_ascii_digit() {
+                val = val * 10 + (line[i] - b'0') as u64;
+                i += 1;
+            }
+            self.numbers.push(Number {
+                val,
+                row,
+                start: start as u32,
+                end: (i - 1) as u32,
+                symbols: Vec::new(),
+            });
+        }
+        self.numbers.len() - before
+    }
+
+    fn parse_symbols(&mut self, row: u32, line: &[u8]) -> usize {
+        let before = self.symbols.len();
+        for (i, &c) in line.iter().enumerate() {
+            if c.is_ascii_digit() || c == b'.' {
+                continue;
+            }
+            self.symbols.push(Symbol {
+                kind: c,
+                row,
+                col: i as u32,
+                numbers: Vec::new(),
+            });
+        }
+        self.symbols.len() - before
+    }
+
+    fn link(&mut self, num: usize, sym: usize) {
+        if self.symbols[sym].touches(&self.numbers[num]) {
+            self.symbols[sym].numbers.push(num);
+            self.numbers[num].symbols.push(sym);
+        }
+    }
+
+    /// Every adjacent `(number, symbol)` pair, as indices.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.numbers
+            .iter()
+            .enumerate()
+            .flat_map(|(n, num)| num.symbols.iter().map(move |&s| (n, s)))
+    }
+
+    pub fn neighbours<'a>(&'a self, symbol: &'a Symbol) -> impl Iterator<Item = &'a Number> + 'a {
+        let numbers = &self.numbers;
+        symbol.numbers.iter().map(move |&n| &numbers[n])
+    }
+
+    /// Numbers adjacent to at least one symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> + '_ {
+        self.numbers.iter().filter(|n| !n.symbols.is_empty())
+    }
+
+    /// Numbers adjacent to at least one symbol of the given kind, such as `b'#'`.
+    pub fn numbers_adjacent_to(&self, kind: u8) -> impl Iterator<Item = &Number> + '_ {
+        self.numbers
+            .iter()
+            .filter(move |n| n.symbols.iter().any(|&s| self.symbols[s].kind == kind))
+    }
+
+    /// Symbols with exactly `k` adjacent numbers.
+    pub fn symbols_with_neighbours(&self, k: usize) -> impl Iterator<Item = &Symbol> + '_ {
+        self.symbols.iter().filter(move |s| s.numbers.len() == k)
+    }
+
+    /// The ratio of every symbol of `kind` that is a gear according to `rule`.
+    ///
+    /// With `b'*'` these are the gears of the puzzle, but any symbol kind may act as a gear.
+    pub fn gear_ratios(
+        &self,
+        kind: u8,
+        rule: GearRule,
+    ) -> impl Iterator<Item = (&Symbol, u64)> + '_ {
+        self.symbols
+            .iter()
+            .filter(move |s| s.kind == kind)
+            .filter_map(move |s| {
+                let neighbours = self.neighbours(s).map(|n| n.val).collect();
+                rule.ratio(&neighbours).map(|r| (s, r))
+            })
+    }
+
+    /// Symbols of `kind` whose neighbour count does not make them a gear according to `rule`.
+    pub fn gear_mismatches(&self, kind: u8, rule: GearRule) -> impl Iterator<Item = &Symbol> + '_ {
+        self.symbols
+            .iter()
+            .filter(move |s| s.kind == kind && !rule.matches(s.numbers.len()))
+    }
+}
+
+/// Aggregates of the numbers adjacent to a symbol, collected without allocating.
+///
+/// Every rule only needs some of them, so the others are `None` once they overflow instead of
+/// failing for a symbol that is not even a gear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Neighbours {
+    pub count: usize,
+    pub sum: Option<u64>,
+    /// Sum of the products of every pair of neighbours.
+    pub pairwise: Option<u64>,
+    pub product: Option<u64>,
+}
+
+impl FromIterator<u64> for Neighbours {
+    fn from_iter<T: IntoIterator<Item = u64>>(iter: T) -> Self {
+        let mut res = Neighbours {
+            count: 0,
+            sum: Some(0),
+            pairwise: Some(0),
+            product: Some(1),
+        };
+        for val in iter {
+            res.count += 1;
+            res.pairwise = res
+                .pairwise
+                .zip(res.sum)
+                .and_then(|(pairwise, sum)| pairwise.checked_add(sum.checked_mul(val)?));
+            res.sum = res.sum.and_then(|sum| sum.checked_add(val));
+            res.product = res.product.and_then(|product| product.checked_mul(val));
+        }
+        res
+    }
+}
+
+/// Which symbols count as gears and how their ratio is computed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GearRule {
+    /// Exactly two neighbours, multiplied together.
+    #[default]
+    ExactlyTwo,
+    /// At least two neighbours, summing the products of every pair.
+    AtLeastTwo,
+    /// At least two neighbours, all multiplied together.
+    ProductOfAll,
+}
+
+impl GearRule {
+    pub fn matches(self, count: usize) -> bool {
+        match self {
+            GearRule::ExactlyTwo => count == 2,
+            GearRule::AtLeastTwo | GearRule::ProductOfAll => count >= 2,
+        }
+    }
+
+    /// The ratio of a gear with these neighbours, or `None` if it is not a gear.
+    ///
+    /// Panics if the ratio does not fit in a `u64`.
+    pub fn ratio(self, neighbours: &Neighbours) -> Option<u64> {
+        if !self.matches(neighbours.count) {
+            return None;
+        }
+        let ratio = match self {
+            GearRule::ExactlyTwo | GearRule::AtLeastTwo => neighbours.pairwise,
+            GearRule::ProductOfAll => neighbours.product,
+        };
+        Some(ratio.unwrap_or_else(|| {
+            panic!(
+                "Gear ratio ({}) of {} neighbours overflowed",
+                self, neighbours.count
+            )
+        }))
+    }
+}
+
+impl FromStr for GearRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "exactly-two" => Ok(GearRule::ExactlyTwo),
+            "at-least-two" => Ok(GearRule::AtLeastTwo),
+            "product-of-all" => Ok(GearRule::ProductOfAll),
+            _ => Err(format!("Unknown gear rule: {:?}", s)),
+        }
+    }
+}
+
+impl Display for GearRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            GearRule::ExactlyTwo => "exactly-two",
+            GearRule::AtLeastTwo => "at-least-two",
+            GearRule::ProductOfAll => "product-of-all",
+        })
+    }
+}
+
+impl Task for Schematic {
+    type Input<'a> = &'a [u8] where Self: 'a;
+
+    type Output<'a> = u64 where Self: 'a;
+
+    fn parse<'a>(&self, line: &'a str) -> Self::Input<'a> {
+        line.as_bytes()
+    }
+
+    fn process(&mut self, input: Self::Input<'_>) {
+        self.push_row(input);
+    }
+
+    fn output(&mut self) -> Self::Output<'_> {
+        self.part_numbers().map(|n| n.val).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    #[test]
+    fn example_queries() {
+        let schematic = Schematic::parse(EXAMPLE);
+        assert_eq!(schematic.rows(), 12);
+        assert_eq!(schematic.part_numbers().map(|n| n.val).sum::<u64>(), 4373);
+        let ratios = schematic.gear_ratios(b'*', GearRule::ExactlyTwo);
+        assert_eq!(ratios.map(|(_, r)| r).sum::<u64>(), 467835);
+        let hashed = schematic.numbers_adjacent_to(b'#').map(|n| n.val);
+        assert_eq!(hashed.collect::<Vec<_>>(), [633]);
+        let pairs = schematic
+            .symbols_with_neighbours(2)
+            .map(|s| (s.kind, s.row, s.col))
+            .collect::<Vec<_>>();
+        assert_eq!(pairs, [(b'*', 1, 3), (b'*', 8, 5), (b'-', 10, 7)]);
+        assert_eq!(schematic.symbols_with_neighbours(0).count(), 0);
+        let dashes = schematic.gear_ratios(b'-', GearRule::ExactlyTwo);
+        assert_eq!(dashes.map(|(_, r)| r).collect::<Vec<_>>(), [598 * 12]);
+        let mut schematic_task = Schematic::default();
+        assert_eq!(schematic_task.run_str(EXAMPLE), 4373);
+    }
+
+    #[test]
+    fn edges_are_symmetric() {
+        let schematic = Schematic::parse(EXAMPLE);
+        let edges = schematic.edges().collect::<Vec<_>>();
+        let from_symbols = schematic
+            .symbols
+            .iter()
+            .map(|s| s.numbers.len())
+            .sum::<usize>();
+        assert_eq!(edges.len(), from_symbols);
+        for (n, s) in edges {
+            assert!(schematic.symbols[s].numbers.contains(&n));
+            assert!(schematic.symbols[s].touches(&schematic.numbers[n]));
+        }
+    }
+
+    #[test]
+    fn numbers_at_row_ends() {
+        let schematic = Schematic::parse("12.*\n..34\n#..5\n");
+        let spans = schematic
+            .numbers
+            .iter()
+            .map(|n| (n.val, n.row, n.start, n.end))
+            .collect::<Vec<_>>();
+        assert_eq!(spans, [(12, 0, 0, 1), (34, 1, 2, 3), (5, 2, 3, 3)]);
+        let parts = schematic.part_numbers().map(|n| n.val).collect::<Vec<_>>();
+        assert_eq!(parts, [34]);
+        assert_eq!(schematic.symbols[1].kind, b'#');
+        assert!(schematic.symbols[1].numbers.is_empty());
+
+        // a symbol at the end of a row does not touch a number at the start of the next
+        let schematic = Schematic::parse("..*\n4..\n");
+        assert_eq!(schematic.part_numbers().count(), 0);
+        let schematic = Schematic::parse(".*\n4.\n");
+        assert_eq!(schematic.part_numbers().count(), 1);
+    }
+}
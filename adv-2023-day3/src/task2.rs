@@ -0,0 +1,268 @@
+use crate::{GearRule, Neighbours};
+use adv_2023_common::{input, Task};
+
+struct ParsedLine {
+    pub nums: Vec<NumSpan>,
+    pub gears: Vec<u32>,
+}
+
+impl ParsedLine {
+    pub fn parse(line: &str) -> Self {
+        ParsedLine {
+            nums: Self::parse_num(line.as_bytes()),
+            gears: Self::parse_gears(line.as_bytes()),
+        }
+    }
+
+    fn parse_num(line: &[u8]) -> Vec<NumSpan> {
+        let mut active = false;
+        let mut nums = Vec::with_capacity(16);
+        let mut val = 0;
+        let mut start = 0;
+        let mut end = 0;
+        let mut i = 0;
+        while i < line.len() {
+            let c = line[i];
+            if c.is_ascii_digit() {
+                if active {
+                    end = i;
+                    val = val * 10 + (c - b'0') as u64;
+                } else {
+                    active = true;
+                    start = i;
+                    end = i;
+                    val = (c - b'0') as u64;
+                }
+            } else if active {
+                active = false;
+                nums.push(NumSpan {
+                    val,
+                    start: start as u32,
+                    end: end as u32,
+                });
+            }
+            i += 1;
+        }
+        if active {
+            nums.push(NumSpan {
+                val,
+                start: start as u32,
+                end: end as u32,
+            });
+        }
+        nums
+    }
+
+    fn parse_gears(line: &[u8]) -> Vec<u32> {
+        let mut syms = Vec::with_capacity(16);
+        let mut i = 0;
+        while i < line.len() {
+            let c = line[i];
+            if c != b'*' {
+                i += 1;
+                continue;
+            }
+            syms.push(i as u32);
+            i += 1;
+        }
+        syms
+    }
+}
+
+struct NumSpan {
+    pub val: u64,
+    pub start: u32,
+    pub end: u32,
+}
+
+struct State {
+    pub gears: Vec<u32>,
+    pub old_old_nums: Vec<NumSpan>,
+    pub old_nums: Vec<NumSpan>,
+    pub nums: Vec<NumSpan>,
+    pub rule: GearRule,
+    /// `(row, column, neighbour count)` of every `*` that is not a gear under `rule`.
+    pub mismatches: Vec<(u32, u32, usize)>,
+    pub rows: u32,
+    pub sum: u64,
+}
+
+impl State {
+    fn new(rule: GearRule) -> Self {
+        State {
+            nums: Vec::new(),
+            old_old_nums: Vec::new(),
+            old_nums: Vec::new(),
+            gears: Vec::new(),
+            rule,
+            mismatches: Vec::new(),
+            rows: 0,
+            sum: 0,
+        }
+    }
+
+    fn compute(&mut self) {
+        for val in core::mem::take(&mut self.gears) {
+            let rows: [&[NumSpan]; 3] = [&self.old_old_nums, &self.old_nums, &self.nums];
+            let neighbours = Self::adjacent(val, rows).collect::<Neighbours>();
+            match self.rule.ratio(&neighbours) {
+                Some(ratio) => self.sum += ratio,
+                None => self.mismatches.push((self.rows - 1, val, neighbours.count)),
+            }
+        }
+
+        core::mem::swap(&mut self.old_old_nums, &mut self.old_nums);
+        core::mem::swap(&mut self.old_nums, &mut self.nums);
+        self.nums.clear();
+    }
+    /// Values of the numbers adjacent to the gear in column `val`.
+    fn adjacent(val: u32, rows: [&[NumSpan]; 3]) -> impl Iterator<Item = u64> + '_ {
+        rows.into_iter().flat_map(move |row| {
+            row.iter()
+                // spans are ordered by column, so nothing further along the row can touch
+                .take_while(move |num| num.start <= val + 1)
+                .filter(move |num| num.end + 1 >= val)
+                .map(|num| num.val)
+        })
+    }
+}
+
+impl Task for State {
+    type Input<'a> = ParsedLine where Self: 'a;
+
+    type Output<'a> = u64 where Self: 'a;
+
+    fn parse<'a>(&self, line: &'a str) -> Self::Input<'a> {
+        ParsedLine::parse(line)
+    }
+
+    fn process(&mut self, input: Self::Input<'_>) {
+        self.nums = input.nums;
+        self.compute();
+        self.gears = input.gears;
+        self.rows += 1;
+    }
+
+    fn output(&mut self) -> Self::Output<'_> {
+        self.compute();
+        self.sum
+    }
+}
+
+/// Solve with the [`GearRule::ExactlyTwo`] rule of the puzzle.
+pub fn solve(path: &str) -> String {
+    State::new(GearRule::ExactlyTwo).run(path).to_string()
+}
+
+pub fn main() {
+    let rule = std::env::args()
+        .skip_while(|a| a != "--gear-rule")
+        .nth(1)
+        .map(|r| r.parse::<GearRule>().unwrap())
+        .unwrap_or_default();
+    let mut state = State::new(rule);
+    let res = state.run(&input::arg("adv-2023-day3/input/list.txt"));
+    if std::env::args().any(|a| a == "--mismatches") {
+        for (row, col, count) in &state.mismatches {
+            eprintln!(
+                "Not a gear ({}): '*' at line {}, column {} has {} neighbours",
+                rule,
+                row + 1,
+                col + 1,
+                count
+            );
+        }
+    }
+    println!("{}", res);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Schematic;
+    use adv_2023_common::{diff::assert_agree, gen};
+
+    fn run(input: &str) -> u64 {
+        run_with(input, GearRule::ExactlyTwo).0
+    }
+
+    fn run_with(input: &str, rule: GearRule) -> (u64, Vec<(u32, u32, usize)>) {
+        let mut state = State::new(rule);
+        let sum = state.run_str(input);
+        (sum, state.mismatches)
+    }
+
+    #[test]
+    fn example() {
+        assert_eq!(run(include_str!("../input/example.txt")), 467835);
+    }
+
+    #[test]
+    fn gear_in_first_column() {
+        assert_eq!(run("2..\n*..\n3..\n"), 6);
+        assert_eq!(run("...\n*12\n4..\n"), 48);
+    }
+
+    #[test]
+    fn gear_in_last_column() {
+        assert_eq!(run("..2\n..*\n..3\n"), 6);
+        assert_eq!(run("...\n12*\n..4\n"), 48);
+    }
+
+    #[test]
+    fn gear_in_corner() {
+        assert_eq!(run("*7\n5.\n"), 35);
+        assert_eq!(run("9.\n7*\n"), 63);
+    }
+
+    #[test]
+    fn gear_rules() {
+        let input = "2.3\n.*.\n4..\n..*\n";
+        assert_eq!(
+            run_with(input, GearRule::ExactlyTwo),
+            (0, vec![(1, 1, 3), (3, 2, 0)])
+        );
+        assert_eq!(
+            run_with(input, GearRule::AtLeastTwo),
+            (2 * 3 + 2 * 4 + 3 * 4, vec![(3, 2, 0)])
+        );
+        assert_eq!(
+            run_with(input, GearRule::ProductOfAll),
+            (2 * 3 * 4, vec![(3, 2, 0)])
+        );
+
+        // the product of all six overflows, but only product-of-all uses it
+        let input = "1111.2222\n3333*4444\n5555.6666\n";
+        assert_eq!(run_with(input, GearRule::ExactlyTwo), (0, vec![(1, 4, 6)]));
+        assert_eq!(run_with(input, GearRule::AtLeastTwo), (216006175, vec![]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Gear ratio (product-of-all) of 6 neighbours overflowed")]
+    fn product_of_all_reports_overflow() {
+        run_with("1111.2222\n3333*4444\n5555.6666\n", GearRule::ProductOfAll);
+    }
+
+    #[test]
+    fn agrees_with_schematic() {
+        let schematic = |input: &str, rule| {
+            let schematic = Schematic::parse(input);
+            let sum = schematic.gear_ratios(b'*', rule).map(|(_, r)| r).sum();
+            let mismatches = schematic
+                .gear_mismatches(b'*', rule)
+                .map(|s| (s.row, s.col, s.numbers.len()))
+                .collect();
+            (sum, mismatches)
+        };
+        for rule in [
+            GearRule::ExactlyTwo,
+            GearRule::AtLeastTwo,
+            GearRule::ProductOfAll,
+        ] {
+            // part of the outcome, so that a mismatch says which rule it was found with
+            let streamed = |input: &str| (rule, run_with(input, rule));
+            let schematic = |input: &str| (rule, schematic(input, rule));
+            assert_agree(gen::schematic, 40, 50, schematic, streamed);
+        }
+    }
+}
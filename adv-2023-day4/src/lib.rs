@@ -0,0 +1,711 @@
+pub mod task1;
+pub mod task2;
+
+use std::{fmt::Display, str::FromStr};
+
+use adv_2023_common::{Split, Task};
+
+pub struct ParsedLine {
+    pub winning: Vec<u16>,
+    pub has: Vec<u16>,
+}
+
+impl ParsedLine {
+    pub fn parse(line: &str) -> Self {
+        let pos = line.bytes().position(|b| b == b':').unwrap();
+        let (winning, has) = line[pos + 1..].split_once('|').unwrap();
+        ParsedLine {
+            winning: Self::parse_num(winning.trim()),
+            has: Self::parse_num(has.trim()),
+        }
+    }
+
+    fn parse_num(mut line: &str) -> Vec<u16> {
+        let mut nums = Vec::with_capacity(32);
+        loop {
+            line = line.trim_start();
+            if line.is_empty() {
+                nums.sort_unstable();
+                return nums;
+            }
+            let (num, rest) = if let Some((a, b)) = line.split_once(' ') {
+                (a, b)
+            } else {
+                (line, "")
+            };
+            line = rest;
+            nums.push(num.parse::<u16>().unwrap());
+        }
+    }
+
+    /// Number of the card's numbers that are winning numbers.
+    ///
+    /// A card without any winning numbers, or without any numbers of its own, has no matches.
+    pub fn matches(&self, duplicates: Duplicates) -> u32 {
+        if self.winning.is_empty() || self.has.is_empty() {
+            return 0;
+        }
+        if duplicates == Duplicates::Set {
+            if let (Some(winning), Some(has)) = (bitset(&self.winning), bitset(&self.has)) {
+                return (winning & has).count_ones();
+            }
+        }
+        let mut agg = 0u32;
+        let mut w = 0;
+        let mut h = 0;
+        while w < self.winning.len() && h < self.has.len() {
+            let val = self.winning[w];
+            match val.cmp(&self.has[h]) {
+                std::cmp::Ordering::Equal => {
+                    agg += 1;
+                    w += 1;
+                    h += 1;
+                    if duplicates == Duplicates::Set {
+                        while w < self.winning.len() && self.winning[w] == val {
+                            w += 1;
+                        }
+                        while h < self.has.len() && self.has[h] == val {
+                            h += 1;
+                        }
+                    }
+                }
+                std::cmp::Ordering::Less => w += 1,
+                std::cmp::Ordering::Greater => h += 1,
+            }
+        }
+        agg
+    }
+}
+
+/// Bitset of sorted numbers, if they are all small enough to fit.
+fn bitset(nums: &[u16]) -> Option<u128> {
+    if *nums.last()? >= 128 {
+        return None;
+    }
+    Some(nums.iter().fold(0, |acc, &n| acc | 1 << n))
+}
+
+/// How numbers occurring more than once on a card are matched.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Duplicates {
+    /// Every distinct number matches at most once.
+    #[default]
+    Set,
+    /// Every occurrence matches, as long as the other list has as many of it.
+    Multiset,
+}
+
+/// How the number of matches on a card is turned into points, or into the number of cards won.
+#[derive(Debug, Clone)]
+pub enum Scoring {
+    /// One for the first match, doubled for every further match.
+    Doubling,
+    /// One for every match.
+    Linear,
+    /// The Fibonacci sequence `1, 1, 2, 3, 5, ...` for one or more matches.
+    Fibonacci,
+    /// The score for `0, 1, 2, ...` matches, repeating the last entry for any further matches.
+    Table(Vec<u64>),
+    Custom(fn(u32) -> u64),
+}
+
+impl Scoring {
+    /// The score for `matches`, or `None` if it does not fit in a `u64`.
+    pub fn score(&self, matches: u32) -> Option<u64> {
+        match self {
+            Scoring::Doubling if matches == 0 => Some(0),
+            Scoring::Doubling => 1u64.checked_shl(matches - 1),
+            Scoring::Linear => Some(matches as u64),
+            Scoring::Fibonacci => {
+                if matches == 0 {
+                    return Some(0);
+                }
+                let (mut a, mut b) = (0u64, 1u64);
+                for _ in 1..matches {
+                    (a, b) = (b, a.checked_add(b)?);
+                }
+                Some(b)
+            }
+            Scoring::Table(table) => {
+                let idx = (matches as usize).min(table.len().saturating_sub(1));
+                Some(table.get(idx).copied().unwrap_or(0))
+            }
+            Scoring::Custom(f) => Some(f(matches)),
+        }
+    }
+}
+
+impl FromStr for Scoring {
+    type Err = String;
+
+    /// Parses `doubling`, `linear`, `fibonacci` or a table such as `table:0,1,3,6`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "doubling" => Ok(Scoring::Doubling),
+            "linear" => Ok(Scoring::Linear),
+            "fibonacci" => Ok(Scoring::Fibonacci),
+            _ => {
+                let table = s
+                    .strip_prefix("table:")
+                    .ok_or_else(|| format!("Unknown scoring rule: {:?}", s))?;
+                table
+                    .split(',')
+                    .map(|n| n.trim().parse::<u64>())
+                    .collect::<Result<_, _>>()
+                    .map(Scoring::Table)
+                    .map_err(|e| format!("Invalid scoring table {:?}: {}", table, e))
+            }
+        }
+    }
+}
+
+/// Sums the points of every card, as scored by `scoring`.
+#[derive(Debug, Task)]
+#[task(input = ParsedLine, process = Self::add)]
+pub struct Points {
+    pub scoring: Scoring,
+    pub duplicates: Duplicates,
+    #[task(output)]
+    pub sum: u64,
+}
+
+impl Default for Points {
+    fn default() -> Self {
+        Self {
+            scoring: Scoring::Doubling,
+            duplicates: Duplicates::default(),
+            sum: 0,
+        }
+    }
+}
+
+impl Points {
+    fn add(&mut self, card: ParsedLine) {
+        let matches = card.matches(self.duplicates);
+        self.sum = self
+            .scoring
+            .score(matches)
+            .and_then(|points| self.sum.checked_add(points))
+            .expect("points exceed u64::MAX");
+    }
+}
+
+impl Split for Points {
+    fn split(&self, _line: usize) -> Self {
+        Self {
+            scoring: self.scoring.clone(),
+            duplicates: self.duplicates,
+            sum: 0,
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.sum = self
+            .sum
+            .checked_add(other.sum)
+            .expect("points exceed u64::MAX");
+    }
+}
+
+/// Copies of a card won by all copies of an earlier card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CascadeStep {
+    /// Index of the card whose matches won the copies.
+    pub source: usize,
+    /// Index of the card that was copied.
+    pub target: usize,
+    pub copies: u64,
+}
+
+/// Simulates how scratchcards win copies of the cards below them.
+///
+/// Cards are indexed from `0` in input order, so index `0` is `Card 1`. Each card wins one copy of
+/// as many following cards as `scoring` gives for its matches.
+#[derive(Debug)]
+pub struct Cascade {
+    /// Matching numbers on each card.
+    pub matches: Vec<u32>,
+    /// Total number of instances of each card, including the original.
+    pub copies: Vec<u64>,
+    /// Every transfer of copies, ordered by the card that received them.
+    pub trace: Vec<CascadeStep>,
+    pub duplicates: Duplicates,
+    pub scoring: Scoring,
+    /// Copies won by earlier cards that still reach the cards to come.
+    active: Vec<Won<u64>>,
+    overflow: Option<usize>,
+}
+
+/// Copies of every card from the one after `source` up to, but not including, `end`.
+#[derive(Debug)]
+struct Won<N> {
+    source: usize,
+    end: usize,
+    copies: N,
+}
+
+impl Default for Cascade {
+    fn default() -> Self {
+        Self {
+            matches: Vec::new(),
+            copies: Vec::new(),
+            trace: Vec::new(),
+            duplicates: Duplicates::default(),
+            scoring: Scoring::Linear,
+            active: Vec::new(),
+            overflow: None,
+        }
+    }
+}
+
+impl Cascade {
+    /// Add the next card, which has `matches` matching numbers.
+    ///
+    /// Cards won past the last card do not exist, so they are neither counted nor traced. Copy
+    /// counts that do not fit in a `u64` saturate, and make [`Cascade::total`] fail.
+    pub fn push(&mut self, matches: u32) {
+        let target = self.copies.len();
+        self.active.retain(|won| won.end > target);
+        let mut overflow = false;
+        let mut copies = 1u64;
+        for won in &self.active {
+            copies = copies.checked_add(won.copies).unwrap_or_else(|| {
+                overflow = true;
+                u64::MAX
+            });
+            self.trace.push(CascadeStep {
+                source: won.source,
+                target,
+                copies: won.copies,
+            });
+        }
+        if overflow && self.overflow.is_none() {
+            self.overflow = Some(target);
+        }
+        self.matches.push(matches);
+        self.copies.push(copies);
+        let end = self.end(target, matches);
+        if end > target + 1 {
+            self.active.push(Won {
+                source: target,
+                end,
+                copies,
+            });
+        }
+    }
+
+    /// Index after the last card won by the card at `source` with `matches` matches.
+    fn end(&self, source: usize, matches: u32) -> usize {
+        let won = self.scoring.score(matches).unwrap_or(u64::MAX);
+        let won = usize::try_from(won).unwrap_or(usize::MAX);
+        (source + 1).saturating_add(won)
+    }
+
+    /// Total number of scratchcards, originals and copies.
+    pub fn total(&self) -> Result<u64, Overflow> {
+        if let Some(card) = self.overflow {
+            return Err(Overflow::Card(card));
+        }
+        self.copies
+            .iter()
+            .try_fold(0u64, |acc, &c| acc.checked_add(c))
+            .ok_or(Overflow::Total)
+    }
+
+    /// Total number of scratchcards, recomputed without any upper limit.
+    #[cfg(feature = "bigint")]
+    pub fn total_big(&self) -> num_bigint::BigUint {
+        use num_bigint::BigUint;
+        let mut active = Vec::<Won<BigUint>>::new();
+        let mut total = BigUint::default();
+        for (target, &matches) in self.matches.iter().enumerate() {
+            active.retain(|won| won.end > target);
+            let copies = active
+                .iter()
+                .fold(BigUint::from(1u32), |acc, won| acc + &won.copies);
+            let end = self.end(target, matches);
+            total += &copies;
+            if end > target + 1 {
+                active.push(Won {
+                    source: target,
+                    end,
+                    copies,
+                });
+            }
+        }
+        total
+    }
+
+    /// The transfers that `card` received copies from.
+    pub fn sources(&self, card: usize) -> impl Iterator<Item = &CascadeStep> + '_ {
+        self.trace.iter().filter(move |s| s.target == card)
+    }
+}
+
+/// A copy count that does not fit in a `u64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// The copies of the card at this index, or of a card it was won from, overflowed.
+    Card(usize),
+    /// Every card count fits, but their sum does not.
+    Total,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Overflow::Card(card) => write!(f, "copies of card {} exceed u64::MAX", card + 1),
+            Overflow::Total => write!(f, "total number of cards exceeds u64::MAX"),
+        }
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// The total of a [`Cascade`], which only needs a big integer once it no longer fits in a `u64`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Total {
+    Exact(u64),
+    #[cfg(feature = "bigint")]
+    Big(num_bigint::BigUint),
+}
+
+impl Display for Total {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Total::Exact(n) => n.fmt(f),
+            #[cfg(feature = "bigint")]
+            Total::Big(n) => n.fmt(f),
+        }
+    }
+}
+
+impl Task for Cascade {
+    type Input<'a> = ParsedLine where Self: 'a;
+
+    type Output<'a> = Total where Self: 'a;
+
+    fn parse<'a>(&self, line: &'a str) -> Self::Input<'a> {
+        ParsedLine::parse(line)
+    }
+
+    fn process(&mut self, input: Self::Input<'_>) {
+        self.push(input.matches(self.duplicates));
+    }
+
+    fn output(&mut self) -> Self::Output<'_> {
+        match self.total() {
+            Ok(total) => Total::Exact(total),
+            #[cfg(feature = "bigint")]
+            Err(_) => Total::Big(self.total_big()),
+            #[cfg(not(feature = "bigint"))]
+            Err(e) => panic!("{} (enable the `bigint` feature to compute it anyway)", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use adv_2023_common::{
+        diff::{assert_agree, feed},
+        gen::{self, Config},
+    };
+
+    /// Generated scratchcards with every number moved past what fits in a bitset.
+    fn large(config: &Config) -> String {
+        gen::scratchcards(config)
+            .lines()
+            .map(|line| {
+                let (card, numbers) = line.split_once(':').unwrap();
+                let numbers = numbers
+                    .split(' ')
+                    .map(|n| {
+                        n.parse::<u16>()
+                            .map_or(n.to_string(), |n| (n + 1000).to_string())
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                format!("{}:{}\n", card, numbers)
+            })
+            .collect()
+    }
+
+    fn intersection(input: &str) -> Vec<u32> {
+        input
+            .lines()
+            .map(|line| {
+                let card = ParsedLine::parse(line);
+                let winning: BTreeSet<_> = card.winning.iter().collect();
+                let has: BTreeSet<_> = card.has.iter().collect();
+                winning.intersection(&has).count() as u32
+            })
+            .collect()
+    }
+
+    fn matches(input: &str) -> Vec<u32> {
+        input
+            .lines()
+            .map(|line| ParsedLine::parse(line).matches(Duplicates::Set))
+            .collect()
+    }
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    fn card(winning: &[u16], has: &[u16]) -> ParsedLine {
+        let sorted = |nums: &[u16]| {
+            let mut nums = nums.to_vec();
+            nums.sort_unstable();
+            nums
+        };
+        ParsedLine {
+            winning: sorted(winning),
+            has: sorted(has),
+        }
+    }
+
+    #[test]
+    fn duplicates_as_set_or_multiset() {
+        let both = |winning: &[u16], has: &[u16]| {
+            let card = card(winning, has);
+            (
+                card.matches(Duplicates::Set),
+                card.matches(Duplicates::Multiset),
+            )
+        };
+        assert_eq!(both(&[5, 5, 7], &[5, 9]), (1, 1));
+        assert_eq!(both(&[5], &[5, 5]), (1, 1));
+        assert_eq!(both(&[5, 5, 7], &[5, 5, 5, 7]), (2, 3));
+        // the same above what fits in a bitset
+        assert_eq!(both(&[500, 500, 700], &[500, 500, 500, 700]), (2, 3));
+    }
+
+    #[test]
+    fn empty_lists_have_no_matches() {
+        for line in ["Card 1: | 1 2", "Card 1: 1 2 |", "Card 1: |"] {
+            let card = ParsedLine::parse(line);
+            assert_eq!(card.matches(Duplicates::Set), 0);
+            assert_eq!(card.matches(Duplicates::Multiset), 0);
+        }
+        assert_eq!(Points::default().run_str("Card 1: | 1 2\nCard 2: 3 |\n"), 0);
+    }
+
+    #[test]
+    fn bitset_boundary() {
+        assert_eq!(bitset(&[0, 127]), Some(1 | 1 << 127));
+        assert_eq!(bitset(&[0, 128]), None);
+        assert_eq!(bitset(&[]), None);
+        for (winning, has, expected) in [
+            (&[1, 127][..], &[127, 3][..], 1),
+            (&[1, 128], &[128, 3], 1),
+            (&[127, 128], &[126, 127, 128], 2),
+            (&[0, 127], &[0, 127], 2),
+        ] {
+            let card = card(winning, has);
+            assert_eq!(card.matches(Duplicates::Set), expected);
+            assert_eq!(card.matches(Duplicates::Multiset), expected);
+        }
+    }
+
+    #[test]
+    fn scoring_rules() {
+        let scores = |scoring: Scoring, matches: &[u32]| {
+            matches
+                .iter()
+                .map(|&m| scoring.score(m))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            scores(Scoring::Doubling, &[0, 1, 4, 64, 65]),
+            [Some(0), Some(1), Some(8), Some(1 << 63), None]
+        );
+        assert_eq!(
+            scores(Scoring::Linear, &[0, 1, 4, u32::MAX]),
+            [Some(0), Some(1), Some(4), Some(u32::MAX as u64)]
+        );
+        assert_eq!(
+            scores(Scoring::Fibonacci, &[0, 1, 2, 3, 5, 93, 94]),
+            [
+                Some(0),
+                Some(1),
+                Some(1),
+                Some(2),
+                Some(5),
+                Some(12200160415121876738),
+                None
+            ]
+        );
+        // the last entry repeats for matches past the end of the table
+        assert_eq!(
+            scores(Scoring::Table(vec![0, 1, 3, 6]), &[0, 2, 3, 4, 10]),
+            [Some(0), Some(3), Some(6), Some(6), Some(6)]
+        );
+        assert_eq!(
+            scores(Scoring::Table(Vec::new()), &[0, 3]),
+            [Some(0), Some(0)]
+        );
+        assert_eq!(scores(Scoring::Custom(|m| m as u64 * 10), &[2]), [Some(20)]);
+    }
+
+    #[test]
+    fn scoring_from_str() {
+        assert!(matches!("doubling".parse(), Ok(Scoring::Doubling)));
+        assert!(matches!("linear".parse(), Ok(Scoring::Linear)));
+        assert!(matches!("fibonacci".parse(), Ok(Scoring::Fibonacci)));
+        match "table:0, 1,3".parse() {
+            Ok(Scoring::Table(table)) => assert_eq!(table, [0, 1, 3]),
+            other => panic!("expected a table, got {:?}", other),
+        }
+        let error = |s: &str| s.parse::<Scoring>().unwrap_err();
+        assert_eq!(error("triangular"), "Unknown scoring rule: \"triangular\"");
+        assert!(error("table:1,x").starts_with("Invalid scoring table \"1,x\": "));
+        assert!(error("table:").starts_with("Invalid scoring table \"\": "));
+        assert!(error("table:-1").starts_with("Invalid scoring table \"-1\": "));
+    }
+
+    #[test]
+    fn example_with_other_rules() {
+        let points = |scoring: Scoring| {
+            Points {
+                scoring,
+                ..Points::default()
+            }
+            .run_str(EXAMPLE)
+        };
+        assert_eq!(points(Scoring::Doubling), 13);
+        assert_eq!(points(Scoring::Linear), 4 + 2 + 2 + 1);
+        assert_eq!(points("table:0,5,6".parse().unwrap()), 5 + 6 + 6 + 6);
+
+        let cascade = cascade(Scoring::Fibonacci, EXAMPLE);
+        assert_eq!(cascade.copies, [1, 2, 4, 6, 7, 1]);
+        assert_eq!(cascade.total(), Ok(21));
+    }
+
+    fn cascade(scoring: Scoring, input: &str) -> Cascade {
+        let mut cascade = Cascade {
+            scoring,
+            ..Cascade::default()
+        };
+        feed(&mut cascade, input);
+        cascade
+    }
+
+    #[test]
+    fn cascade_audit_on_example() {
+        let cascade = cascade(Scoring::Linear, EXAMPLE);
+        assert_eq!(cascade.matches, [4, 2, 2, 1, 0, 0]);
+        assert_eq!(cascade.copies, [1, 2, 4, 8, 14, 1]);
+        assert_eq!(cascade.total(), Ok(30));
+        let step = |source, target, copies| CascadeStep {
+            source,
+            target,
+            copies,
+        };
+        assert_eq!(
+            cascade.trace,
+            [
+                step(0, 1, 1),
+                step(0, 2, 1),
+                step(1, 2, 2),
+                step(0, 3, 1),
+                step(1, 3, 2),
+                step(2, 3, 4),
+                step(0, 4, 1),
+                step(2, 4, 4),
+                step(3, 4, 8),
+            ]
+        );
+        let sources = |card| cascade.sources(card).map(|s| s.source).collect::<Vec<_>>();
+        assert_eq!(sources(0), []);
+        assert_eq!(sources(4), [0, 2, 3]);
+        assert_eq!(sources(5), []);
+        // every card has its original plus what it received
+        for (card, &copies) in cascade.copies.iter().enumerate() {
+            assert_eq!(
+                copies,
+                1 + cascade.sources(card).map(|s| s.copies).sum::<u64>()
+            );
+        }
+    }
+
+    /// Generated cards where some match every winning number, as many as overflow part 2.
+    fn overflowing() -> String {
+        gen::scratchcards(&Config {
+            size: 1000,
+            edge_cases: true,
+            ..Config::default()
+        })
+    }
+
+    #[test]
+    fn cascade_reports_overflow() {
+        let cascade = cascade(Scoring::Linear, &overflowing());
+        let Err(Overflow::Card(card)) = cascade.total() else {
+            panic!("expected an overflowing card, got {:?}", cascade.total());
+        };
+        assert_eq!(cascade.copies[card], u64::MAX);
+        assert!(cascade.copies[..card].iter().all(|&c| c < u64::MAX));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn total_big_goes_past_u64() {
+        let mut example = cascade(Scoring::Linear, EXAMPLE);
+        assert_eq!(example.total_big(), example.total().unwrap().into());
+        assert_eq!(example.output(), Total::Exact(30));
+
+        let mut overflowed = cascade(Scoring::Linear, &overflowing());
+        assert!(overflowed.total().is_err());
+        let big = overflowed.total_big();
+        assert!(big > u64::MAX.into());
+        assert_eq!(overflowed.output(), Total::Big(big));
+    }
+
+    #[test]
+    fn cascade_only_counts_cards_that_exist() {
+        let numbers = (1..=40)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let input = format!("Card 1: {0} | {0}\nCard 2: 1 | 2\n", numbers);
+        for scoring in [
+            Scoring::Doubling,
+            Scoring::Fibonacci,
+            Scoring::Table(vec![u64::MAX]),
+        ] {
+            let cascade = cascade(scoring, &input);
+            assert_eq!(cascade.matches, [40, 0]);
+            assert_eq!(cascade.copies, [1, 2]);
+            assert_eq!(cascade.total(), Ok(3));
+            assert_eq!(
+                cascade.trace,
+                [CascadeStep {
+                    source: 0,
+                    target: 1,
+                    copies: 1
+                }]
+            );
+        }
+
+        // the first card wins 8 cards, but only 5 follow it
+        let cascade = cascade(Scoring::Doubling, EXAMPLE);
+        assert_eq!(cascade.copies, [1, 2, 4, 8, 14, 2]);
+        assert_eq!(cascade.trace.len(), 10);
+        assert!(cascade.trace.iter().all(|s| s.target < 6));
+    }
+
+    #[test]
+    fn parallel_points_match_sequential() {
+        let sequential = |input: &str| Points::default().run_str(input);
+        let parallel = |input: &str| Points::default().run_parallel_str(input, 4);
+        assert_agree(gen::scratchcards, 2000, 4, sequential, parallel);
+    }
+
+    #[test]
+    fn matches_agree_with_set_intersection() {
+        assert_agree(gen::scratchcards, 50, 20, intersection, matches);
+        assert_agree(large, 50, 20, intersection, matches);
+    }
+}
@@ -0,0 +1,19 @@
+use crate::{Points, Scoring};
+use adv_2023_common::{available_threads, input, Split, Task};
+
+/// Solve on the calling thread, leaving it to the caller how many inputs to solve at once.
+pub fn solve(path: &str) -> String {
+    Points::default().run(path).to_string()
+}
+
+pub fn main() {
+    let mut state = Points::default();
+    if let Some(scoring) = std::env::args().skip_while(|a| a != "--scoring").nth(1) {
+        state.scoring = scoring.parse::<Scoring>().unwrap();
+    }
+    let res = state.run_parallel(
+        &input::arg("adv-2023-day4/input/list.txt"),
+        available_threads(),
+    );
+    println!("{}", res);
+}
@@ -0,0 +1,23 @@
+use crate::{Cascade, Scoring};
+use adv_2023_common::{input, Task};
+
+pub fn solve(path: &str) -> String {
+    Cascade::default().run(path).to_string()
+}
+
+pub fn main() {
+    let mut state = Cascade::default();
+    if let Some(scoring) = std::env::args().skip_while(|a| a != "--scoring").nth(1) {
+        state.scoring = scoring.parse::<Scoring>().unwrap();
+    }
+    let res = state.run(&input::arg("adv-2023-day4/input/list.txt"));
+    if std::env::args().any(|a| a == "--trace") {
+        for (card, (matches, copies)) in state.matches.iter().zip(&state.copies).enumerate() {
+            eprintln!("Card {}: {} matches, {} copies", card + 1, matches, copies);
+            for step in state.sources(card) {
+                eprintln!("  {} from card {}", step.copies, step.source + 1);
+            }
+        }
+    }
+    println!("{}", res);
+}
@@ -0,0 +1,2 @@
+pub mod task1;
+pub mod task2;
@@ -0,0 +1,344 @@
+pub mod task1;
+pub mod task2;
+
+use std::{fmt::Display, str::FromStr};
+
+use adv_2023_common::{
+    json::{Object, ToJson},
+    Fold,
+};
+
+pub enum ParsedLine {
+    Times(Vec<u64>),
+    Distances(Vec<u64>),
+}
+
+impl ParsedLine {
+    pub fn parse(line: &str) -> Self {
+        if let Some(line) = line.strip_prefix("Time:") {
+            return ParsedLine::Times(Self::parse_nums(line));
+        }
+        if let Some(line) = line.strip_prefix("Distance:") {
+            return ParsedLine::Distances(Self::parse_nums(line));
+        }
+        panic!("Unknown line: {:?}", line);
+    }
+
+    fn parse_nums(line: &str) -> Vec<u64> {
+        let mut line = line.trim();
+        let mut nums = Vec::with_capacity(32);
+        while let Some(pos) = line.find(' ') {
+            nums.push(line[..pos].parse::<u64>().unwrap());
+            line = line[pos + 1..].trim();
+        }
+        nums.push(line.parse::<u64>().unwrap());
+        nums
+    }
+
+    /// Join the numbers as if the spaces between them were not there, so `7 15 30` is `71530`.
+    pub fn kerned(nums: &[u64]) -> u64 {
+        let mut num = 0;
+        for &n in nums {
+            num = num * 10u64.pow(n.checked_ilog10().unwrap_or(0) + 1) + n;
+        }
+        num
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time: u64,
+    /// The distance to beat.
+    pub record: u64,
+}
+
+/// How the speed of a boat follows from how long its button was held.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Model {
+    /// Every millisecond held adds one millimetre per millisecond of speed.
+    #[default]
+    Linear,
+    /// Every millisecond held adds `rate` millimetres per millisecond of speed.
+    Rate { rate: f64 },
+    /// Like [`Model::Rate`], but the speed never goes beyond `max`.
+    Capped { rate: f64, max: f64 },
+    /// Like [`Model::Rate`], but once released the speed decays by a factor `e^-drag` every
+    /// millisecond.
+    Drag { rate: f64, drag: f64 },
+}
+
+impl Model {
+    /// Distance travelled in a race of `time` milliseconds after holding for `hold`.
+    pub fn distance(&self, hold: u64, time: u64) -> f64 {
+        let moving = time.saturating_sub(hold) as f64;
+        let hold = hold as f64;
+        match *self {
+            Model::Linear => hold * moving,
+            Model::Rate { rate } => rate * hold * moving,
+            Model::Capped { rate, max } => (rate * hold).min(max) * moving,
+            Model::Drag { rate, drag: 0.0 } => rate * hold * moving,
+            Model::Drag { rate, drag } => rate * hold * (1.0 - (-drag * moving).exp()) / drag,
+        }
+    }
+
+    fn beats(&self, race: Race, hold: u64) -> bool {
+        if hold > race.time {
+            return false;
+        }
+        if *self == Model::Linear {
+            // exact, as the puzzle inputs are too large for floats to be trusted
+            return hold as u128 * (race.time - hold) as u128 > race.record as u128;
+        }
+        self.distance(hold, race.time) > race.record as f64
+    }
+
+    /// The shortest and longest hold times that beat the record of `race`, if any.
+    ///
+    /// Models without a closed form are solved numerically. This relies on every model travelling
+    /// furthest for a single hold time and less the further away from it, so the winning hold
+    /// times are always a single range.
+    pub fn winning_holds(&self, race: Race) -> Option<(u64, u64)> {
+        if let Some(holds) = self.closed_form(race) {
+            return holds;
+        }
+        let best = self.best_hold(race);
+        if !self.beats(race, best) {
+            return None;
+        }
+        // bisect for the edges of the winning range on both sides of the best hold
+        let (mut lo, mut hi) = (0, best);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.beats(race, mid) {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        let min = lo;
+        let (mut lo, mut hi) = (best, race.time);
+        while lo < hi {
+            let mid = hi - (hi - lo) / 2;
+            if self.beats(race, mid) {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        Some((min, lo))
+    }
+
+    /// Solve the models where speed is proportional to the hold time, if this is one of them.
+    fn closed_form(&self, race: Race) -> Option<Option<(u64, u64)>> {
+        let rate = match *self {
+            Model::Linear => 1.0,
+            Model::Rate { rate } => rate,
+            Model::Drag { rate, drag: 0.0 } => rate,
+            Model::Capped { .. } | Model::Drag { .. } => return None,
+        };
+        // rate * hold * (time - hold) > record  <=>  hold^2 - time * hold + record / rate < 0
+        let time = race.time as f64;
+        let disc = time * time - 4.0 * race.record as f64 / rate;
+        if disc.is_nan() || disc < 0.0 {
+            return Some(None);
+        }
+        // the root is only approximate for large races, so nudge it onto the exact edge
+        let mut min = ((time - disc.sqrt()) / 2.0).floor().max(0.0) as u64;
+        while min > 0 && self.beats(race, min - 1) {
+            min -= 1;
+        }
+        while min <= race.time / 2 && !self.beats(race, min) {
+            min += 1;
+        }
+        if !self.beats(race, min) {
+            return Some(None);
+        }
+        // the distance is symmetric around half the race time
+        Some(Some((min, race.time - min)))
+    }
+
+    /// Ternary search for the hold time travelling furthest.
+    fn best_hold(&self, race: Race) -> u64 {
+        let distance = |hold| self.distance(hold, race.time);
+        let (mut lo, mut hi) = (0, race.time);
+        while hi - lo > 2 {
+            let m1 = lo + (hi - lo) / 3;
+            let m2 = hi - (hi - lo) / 3;
+            if distance(m1) < distance(m2) {
+                lo = m1 + 1;
+            } else {
+                hi = m2;
+            }
+        }
+        (lo..=hi)
+            .max_by(|&a, &b| distance(a).total_cmp(&distance(b)))
+            .unwrap()
+    }
+}
+
+impl FromStr for Model {
+    type Err = String;
+
+    /// Parses `linear`, `rate:RATE`, `capped:RATE,MAX` or `drag:RATE,DRAG`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, args) = s.split_once(':').unwrap_or((s, ""));
+        let args = args
+            .split(',')
+            .filter(|a| !a.is_empty())
+            .map(|a| a.trim().parse::<f64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Invalid model parameters {:?}: {}", args, e))?;
+        match (name, args.as_slice()) {
+            ("linear", []) => Ok(Model::Linear),
+            ("rate", &[rate]) => Ok(Model::Rate { rate }),
+            ("capped", &[rate, max]) => Ok(Model::Capped { rate, max }),
+            ("drag", &[rate, drag]) => Ok(Model::Drag { rate, drag }),
+            _ => Err(format!("Unknown model: {:?}", s)),
+        }
+    }
+}
+
+/// How a single race can be won.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaceResult {
+    pub race: Race,
+    /// The shortest and longest hold times that beat the record, if any.
+    pub holds: Option<(u64, u64)>,
+    /// The number of hold times that beat the record.
+    pub ways: u64,
+}
+
+impl RaceResult {
+    pub fn new(race: Race, model: &Model) -> Self {
+        let holds = model.winning_holds(race);
+        let ways = holds.map_or(0, |(min, max)| max - min + 1);
+        Self { race, holds, ways }
+    }
+}
+
+impl ToJson for RaceResult {
+    fn write_json(&self, out: &mut String) {
+        Object::new(out)
+            .field("time", &self.race.time)
+            .field("record", &self.race.record)
+            .field("min_hold", &self.holds.map(|h| h.0))
+            .field("max_hold", &self.holds.map(|h| h.1))
+            .field("ways", &self.ways)
+            .finish();
+    }
+}
+
+/// The ways to win every race of a sheet, and their product.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub races: Vec<RaceResult>,
+    pub product: u64,
+}
+
+impl Report {
+    pub fn new(races: impl IntoIterator<Item = Race>, model: &Model) -> Self {
+        let races: Vec<_> = races
+            .into_iter()
+            .map(|race| RaceResult::new(race, model))
+            .collect();
+        let product = Fold::Product
+            .fold(races.iter().map(|r| r.ways))
+            .expect("product of ways to win exceeds u64::MAX");
+        Self { races, product }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:>4} {:>12} {:>16} {:>12} {:>12} {:>12}",
+            "race", "time", "record", "min hold", "max hold", "ways"
+        )?;
+        for (i, r) in self.races.iter().enumerate() {
+            let (min, max) = match r.holds {
+                Some((min, max)) => (min.to_string(), max.to_string()),
+                None => ("-".to_string(), "-".to_string()),
+            };
+            writeln!(
+                f,
+                "{:>4} {:>12} {:>16} {:>12} {:>12} {:>12}",
+                i + 1,
+                r.race.time,
+                r.race.record,
+                min,
+                max,
+                r.ways
+            )?;
+        }
+        writeln!(f, "product: {}", self.product)
+    }
+}
+
+impl ToJson for Report {
+    fn write_json(&self, out: &mut String) {
+        Object::new(out)
+            .field("races", &self.races)
+            .field("product", &self.product)
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adv_2023_common::{diff::assert_agree, gen};
+
+    fn races(input: &str) -> Vec<Race> {
+        let mut lines = input.lines().map(ParsedLine::parse);
+        match (lines.next(), lines.next()) {
+            (Some(ParsedLine::Times(times)), Some(ParsedLine::Distances(records))) => times
+                .into_iter()
+                .zip(records)
+                .map(|(time, record)| Race { time, record })
+                .collect(),
+            _ => panic!("Expected times and then distances"),
+        }
+    }
+
+    /// Try every hold time.
+    fn scan(input: &str, model: Model) -> Vec<u64> {
+        races(input)
+            .into_iter()
+            .map(|race| {
+                (0..=race.time)
+                    .filter(|&hold| model.distance(hold, race.time) > race.record as f64)
+                    .count() as u64
+            })
+            .collect()
+    }
+
+    fn solved(input: &str, model: Model) -> Vec<u64> {
+        races(input)
+            .into_iter()
+            .map(|race| RaceResult::new(race, &model).ways)
+            .collect()
+    }
+
+    #[test]
+    fn winning_holds_match_a_scan() {
+        let models = [
+            Model::Linear,
+            Model::Rate { rate: 2.5 },
+            Model::Capped {
+                rate: 1.5,
+                max: 20.0,
+            },
+            Model::Drag {
+                rate: 1.0,
+                drag: 0.05,
+            },
+        ];
+        for model in models {
+            // part of the outcome, so that a mismatch says which model it was found with
+            let scan = |input: &str| (model, scan(input, model));
+            let solved = |input: &str| (model, solved(input, model));
+            assert_agree(gen::races, 10, 50, scan, solved);
+        }
+    }
+}
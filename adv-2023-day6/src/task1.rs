@@ -0,0 +1,66 @@
+use crate::{Model, ParsedLine, Race, Report};
+use adv_2023_common::{input, json::ToJson, Task};
+
+#[derive(Debug, Default)]
+struct State {
+    times: Vec<u64>,
+    distances: Vec<u64>,
+    model: Model,
+}
+
+impl State {
+    fn report(&self) -> Report {
+        Report::new(
+            self.times
+                .iter()
+                .zip(&self.distances)
+                .map(|(&time, &record)| Race { time, record }),
+            &self.model,
+        )
+    }
+}
+
+impl Task for State {
+    type Input<'a> = ParsedLine where Self: 'a;
+
+    type Output<'a> = u64 where Self: 'a;
+
+    fn parse<'a>(&self, line: &'a str) -> Self::Input<'a> {
+        ParsedLine::parse(line)
+    }
+
+    fn process(&mut self, input: Self::Input<'_>) {
+        match input {
+            ParsedLine::Times(a) => {
+                self.times = a;
+            }
+            ParsedLine::Distances(a) => {
+                self.distances = a;
+            }
+        }
+    }
+
+    fn output(&mut self) -> Self::Output<'_> {
+        self.report().product
+    }
+}
+
+pub fn solve(path: &str) -> String {
+    State::default().run(path).to_string()
+}
+
+pub fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+    let mut state = State::default();
+    if let Some(model) = args.iter().skip_while(|a| *a != "--model").nth(1) {
+        state.model = model.parse::<Model>().unwrap();
+    }
+    let res = state.run(&input::arg("adv-2023-day6/input/list.txt"));
+    if args.iter().any(|a| a == "--report") {
+        print!("{}", state.report());
+    } else if args.iter().any(|a| a == "--json") {
+        println!("{}", state.report().to_json());
+    } else {
+        println!("{}", res);
+    }
+}
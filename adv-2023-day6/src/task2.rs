@@ -0,0 +1,53 @@
+use crate::{Model, ParsedLine, Race};
+use adv_2023_common::{input, Task};
+
+#[derive(Debug, Default)]
+struct State {
+    time: u64,
+    distance: u64,
+    model: Model,
+}
+
+impl Task for State {
+    type Input<'a> = ParsedLine where Self: 'a;
+
+    type Output<'a> = u64 where Self: 'a;
+
+    fn parse<'a>(&self, line: &'a str) -> Self::Input<'a> {
+        ParsedLine::parse(line)
+    }
+
+    fn process(&mut self, input: Self::Input<'_>) {
+        match input {
+            ParsedLine::Times(a) => {
+                self.time = ParsedLine::kerned(&a);
+            }
+            ParsedLine::Distances(a) => {
+                self.distance = ParsedLine::kerned(&a);
+            }
+        }
+    }
+
+    fn output(&mut self) -> Self::Output<'_> {
+        let race = Race {
+            time: self.time,
+            record: self.distance,
+        };
+        self.model
+            .winning_holds(race)
+            .map_or(0, |(min, max)| max - min + 1)
+    }
+}
+
+pub fn solve(path: &str) -> String {
+    State::default().run(path).to_string()
+}
+
+pub fn main() {
+    let mut state = State::default();
+    if let Some(model) = std::env::args().skip_while(|a| a != "--model").nth(1) {
+        state.model = model.parse::<Model>().unwrap();
+    }
+    let res = state.run(&input::arg("adv-2023-day6/input/list.txt"));
+    println!("{}", res);
+}
@@ -0,0 +1,78 @@
+//! Compares ranking hands through a `BTreeMap` with the radix sorts on generated hands.
+//!
+//! Run with `cargo run --release -p adv-2023-day7 --example sort_bench [hands] [threads]`.
+
+use std::{collections::BTreeMap, time::Instant};
+
+use adv_2023_day7::{pack, par_radix_sort, radix_sort, winnings};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let count = args.next().map_or(1_000_000, |n| n.parse::<usize>().unwrap());
+    let threads = args.next().map_or_else(
+        || std::thread::available_parallelism().map_or(1, |n| n.get()),
+        |n| n.parse::<usize>().unwrap(),
+    );
+
+    let mut seed = 0x2023_0007_u64;
+    let mut next = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed
+    };
+    let hands: Vec<(u32, u32)> = (0..count)
+        .map(|_| {
+            let mut cards = [0u8; 5];
+            cards.iter_mut().for_each(|c| *c = (next() % 13) as u8);
+            (pack(hand_type(&cards), cards), (next() % 1000) as u32 + 1)
+        })
+        .collect();
+    println!("{} hands, {} threads", count, threads);
+
+    // there are far fewer distinct hands than a million, so the input index breaks ties
+    let time = Instant::now();
+    let map: BTreeMap<(u32, usize), u32> = hands
+        .iter()
+        .enumerate()
+        .map(|(i, &(hand, bid))| ((hand, i), bid))
+        .collect();
+    let res_map: u64 = map
+        .values()
+        .enumerate()
+        .map(|(n, &bid)| (n as u64 + 1) * bid as u64)
+        .sum();
+    println!("btree map:   {:>8}us {}", time.elapsed().as_micros(), res_map);
+
+    let mut sorted = hands.clone();
+    let time = Instant::now();
+    radix_sort(&mut sorted);
+    let res_radix = winnings(&sorted);
+    println!("radix:       {:>8}us {}", time.elapsed().as_micros(), res_radix);
+
+    let mut sorted = hands;
+    let time = Instant::now();
+    par_radix_sort(&mut sorted, threads);
+    let res_par = winnings(&sorted);
+    println!("par radix:   {:>8}us {}", time.elapsed().as_micros(), res_par);
+
+    assert_eq!(res_map, res_radix);
+    assert_eq!(res_map, res_par);
+}
+
+fn hand_type(cards: &[u8; 5]) -> u8 {
+    let mut counts = [0u8; 13];
+    for &card in cards {
+        counts[card as usize] += 1;
+    }
+    counts.sort_by(|c1, c2| c2.cmp(c1));
+    match (counts[0], counts[1]) {
+        (5, _) => 6,
+        (4, _) => 5,
+        (3, 2) => 4,
+        (3, _) => 3,
+        (2, 2) => 2,
+        (2, _) => 1,
+        _ => 0,
+    }
+}
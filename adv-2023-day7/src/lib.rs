@@ -0,0 +1,283 @@
+pub mod task1;
+pub mod task2;
+
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    MissingBid,
+    HandLength(usize),
+    InvalidCard(char),
+    InvalidBid(String),
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorKind::MissingBid => write!(f, "expected a bid after the hand"),
+            ErrorKind::HandLength(n) => write!(f, "expected 5 cards but found {}", n),
+            ErrorKind::InvalidCard(c) => write!(f, "invalid card: {:?}", c),
+            ErrorKind::InvalidBid(bid) => write!(f, "invalid bid: {:?}", bid),
+        }
+    }
+}
+
+/// An error in a hand, whose line is filled in by the task reading it.
+pub type ParseError = adv_2023_common::ParseError<ErrorKind>;
+
+/// Parse a line such as `32T3K 765` into the strength of each card and the bid.
+///
+/// `order` lists the card labels from weakest to strongest, and a card's strength is its index
+/// in it. With `lowercase`, labels are also accepted in lower case.
+pub fn parse_hand(
+    line: &str,
+    order: &[u8; 13],
+    lowercase: bool,
+) -> Result<([u8; 5], u32), ParseError> {
+    let hand_len = line
+        .find(|c: char| c.is_ascii_whitespace())
+        .unwrap_or(line.len());
+    let (hand, rest) = line.split_at(hand_len);
+    let mut cards = [0u8; 5];
+    let mut count = 0;
+    for (i, c) in hand.char_indices() {
+        let label = if lowercase { c.to_ascii_uppercase() } else { c };
+        let strength = order
+            .iter()
+            .position(|&o| o as char == label)
+            .ok_or_else(|| ParseError::new(i + 1, ErrorKind::InvalidCard(c)))?;
+        if count < 5 {
+            cards[count] = strength as u8;
+        }
+        count += 1;
+    }
+    if count != 5 {
+        return Err(ParseError::new(1, ErrorKind::HandLength(count)));
+    }
+    let bid = rest.trim();
+    if bid.is_empty() {
+        return Err(ParseError::new(line.len() + 1, ErrorKind::MissingBid));
+    }
+    let column = line.len() - rest.trim_start().len() + 1;
+    let bid = bid
+        .parse::<u32>()
+        .map_err(|_| ParseError::new(column, ErrorKind::InvalidBid(bid.to_string())))?;
+    Ok((cards, bid))
+}
+
+/// Number of bits used by a single card in a packed hand.
+const CARD_BITS: u32 = 4;
+
+/// Hands below this size are not worth sorting on more than one thread.
+pub const PARALLEL_THRESHOLD: usize = 1 << 16;
+
+/// Pack a hand into a `u32` that orders the same way as the hand itself.
+///
+/// The hand type takes bits 20 to 22, followed by one nibble per card in the order they were
+/// dealt, so both `hand_type` and every card must be below 8 and 16 respectively.
+pub fn pack(hand_type: u8, cards: [u8; 5]) -> u32 {
+    cards
+        .iter()
+        .fold(hand_type as u32, |acc, &c| (acc << CARD_BITS) | c as u32)
+}
+
+/// Stable LSD radix sort of `(packed hand, bid)` pairs by hand, one byte at a time.
+pub fn radix_sort(hands: &mut Vec<(u32, u32)>) {
+    let mut buf = Vec::with_capacity(hands.len());
+    for shift in [0, 8, 16] {
+        counting_sort(hands, &mut buf, shift);
+        core::mem::swap(hands, &mut buf);
+    }
+}
+
+/// Like [`radix_sort`], but splits the hands by their top byte and sorts the buckets on up to
+/// `threads` threads.
+pub fn par_radix_sort(hands: &mut Vec<(u32, u32)>, threads: usize) {
+    let threads = threads.max(1);
+    if threads == 1 || hands.len() < PARALLEL_THRESHOLD {
+        return radix_sort(hands);
+    }
+    let mut buf = Vec::with_capacity(hands.len());
+    let starts = counting_sort(hands, &mut buf, 16);
+
+    // hand out whole buckets to threads so each gets roughly the same number of hands
+    let per_thread = buf.len().div_ceil(threads);
+    let mut chunks = Vec::with_capacity(threads);
+    let mut rest = buf.as_mut_slice();
+    let mut bucket = 0;
+    while !rest.is_empty() {
+        let first = bucket;
+        while bucket < 256 && starts[bucket + 1] - starts[first] < per_thread {
+            bucket += 1;
+        }
+        bucket = (bucket + 1).min(256);
+        let (chunk, tail) = rest.split_at_mut(starts[bucket] - starts[first]);
+        chunks.push((chunk, &starts[first..=bucket]));
+        rest = tail;
+    }
+
+    std::thread::scope(|s| {
+        for (chunk, bounds) in chunks {
+            s.spawn(move || {
+                let mut tmp = Vec::new();
+                let mut out = Vec::new();
+                for b in bounds.windows(2) {
+                    let bucket = &mut chunk[b[0] - bounds[0]..b[1] - bounds[0]];
+                    tmp.clear();
+                    tmp.extend_from_slice(bucket);
+                    for shift in [0, 8] {
+                        counting_sort(&tmp, &mut out, shift);
+                        core::mem::swap(&mut tmp, &mut out);
+                    }
+                    bucket.copy_from_slice(&tmp);
+                }
+            });
+        }
+    });
+    *hands = buf;
+}
+
+/// Stable counting sort of `input` into `output` by the byte at `shift`, returning the start
+/// offset of every byte value followed by the total length.
+fn counting_sort(input: &[(u32, u32)], output: &mut Vec<(u32, u32)>, shift: u32) -> [usize; 257] {
+    let mut starts = [0usize; 257];
+    for &(key, _) in input {
+        starts[((key >> shift) & 0xff) as usize + 1] += 1;
+    }
+    for i in 1..257 {
+        starts[i] += starts[i - 1];
+    }
+    let mut next = starts;
+    output.clear();
+    output.resize(input.len(), (0, 0));
+    for &item in input {
+        let digit = ((item.0 >> shift) & 0xff) as usize;
+        output[next[digit]] = item;
+        next[digit] += 1;
+    }
+    starts
+}
+
+/// Total winnings of hands sorted from weakest to strongest.
+pub fn winnings(sorted: &[(u32, u32)]) -> u64 {
+    sorted
+        .iter()
+        .enumerate()
+        .map(|(n, &(_, bid))| (n as u64 + 1) * bid as u64)
+        .sum()
+}
+
+/// The first pair of neighbouring hands in a sorted list that are the same, if any.
+pub fn duplicate(sorted: &[(u32, u32)]) -> Option<(u32, u32, u32)> {
+    sorted
+        .windows(2)
+        .find(|w| w[0].0 == w[1].0)
+        .map(|w| (w[0].0, w[0].1, w[1].1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adv_2023_common::gen::Rng;
+
+    static ORDER: &[u8; 13] = b"23456789TJQKA";
+
+    fn error(line: &str, lowercase: bool) -> (usize, ErrorKind) {
+        let e = parse_hand(line, ORDER, lowercase).unwrap_err();
+        assert_eq!(e.line, None);
+        (e.column, e.kind)
+    }
+
+    #[test]
+    fn parses_hands() {
+        assert_eq!(
+            parse_hand("32T3K 765", ORDER, false),
+            Ok(([1, 0, 8, 1, 11], 765))
+        );
+        assert_eq!(parse_hand("AAAAA \t 1 ", ORDER, false), Ok(([12; 5], 1)));
+    }
+
+    #[test]
+    fn errors_have_columns() {
+        assert_eq!(error("32T3 765", false), (1, ErrorKind::HandLength(4)));
+        assert_eq!(error("32T3KK 765", false), (1, ErrorKind::HandLength(6)));
+        assert_eq!(error("32X3K 765", false), (3, ErrorKind::InvalidCard('X')));
+        assert_eq!(error("32T3K", false), (6, ErrorKind::MissingBid));
+        assert_eq!(error("32T3K   ", false), (9, ErrorKind::MissingBid));
+        assert_eq!(
+            error("32T3K  x7", false),
+            (8, ErrorKind::InvalidBid("x7".to_string()))
+        );
+    }
+
+    #[test]
+    fn lowercase_only_when_asked() {
+        assert_eq!(error("32t3k 765", false), (3, ErrorKind::InvalidCard('t')));
+        assert_eq!(
+            parse_hand("32t3k 765", ORDER, true),
+            Ok(([1, 0, 8, 1, 11], 765))
+        );
+        assert_eq!(error("32t3x 765", true), (5, ErrorKind::InvalidCard('x')));
+    }
+
+    /// `count` packed hands with bids in input order, so a stable sort keeps equal hands in order.
+    fn random_hands(seed: u64, count: usize, types: u64) -> Vec<(u32, u32)> {
+        let mut rng = Rng::new(seed);
+        (0..count)
+            .map(|bid| {
+                let cards = [(); 5].map(|_| rng.below(13) as u8);
+                (pack(rng.below(types) as u8, cards), bid as u32)
+            })
+            .collect()
+    }
+
+    fn sorted(hands: &[(u32, u32)]) -> Vec<(u32, u32)> {
+        let mut sorted = hands.to_vec();
+        sorted.sort_by_key(|&(hand, _)| hand);
+        sorted
+    }
+
+    #[test]
+    fn radix_sort_is_a_stable_sort() {
+        for (seed, count) in [(1, 0), (2, 1), (3, 1000), (4, PARALLEL_THRESHOLD + 1000)] {
+            let hands = random_hands(seed, count, 7);
+            let mut radix = hands.clone();
+            radix_sort(&mut radix);
+            assert_eq!(radix, sorted(&hands), "{} hands", count);
+        }
+    }
+
+    #[test]
+    fn par_radix_sort_is_a_stable_sort() {
+        // one hand type puts every hand in only a few top byte buckets
+        for (seed, types) in [(5, 7), (6, 1)] {
+            for count in [1000, PARALLEL_THRESHOLD, PARALLEL_THRESHOLD * 2 + 1] {
+                let hands = random_hands(seed, count, types);
+                let expected = sorted(&hands);
+                for threads in [0, 1, 2, 3, 7] {
+                    let mut radix = hands.clone();
+                    par_radix_sort(&mut radix, threads);
+                    assert_eq!(radix, expected, "{} hands on {} threads", count, threads);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn packed_hands_order_by_type_then_cards() {
+        assert!(pack(1, [0; 5]) > pack(0, [12; 5]));
+        assert!(pack(3, [2, 0, 0, 0, 0]) > pack(3, [1, 12, 12, 12, 12]));
+        assert!(pack(3, [2, 0, 0, 0, 1]) > pack(3, [2, 0, 0, 0, 0]));
+        assert_eq!(pack(6, [12; 5]), 0x6c_cccc);
+    }
+
+    #[test]
+    fn finds_duplicates() {
+        assert_eq!(duplicate(&[]), None);
+        assert_eq!(duplicate(&[(1, 10), (2, 20), (3, 30)]), None);
+        assert_eq!(
+            duplicate(&[(1, 10), (2, 20), (2, 25), (3, 30), (3, 35)]),
+            Some((2, 20, 25))
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{duplicate, pack, par_radix_sort, parse_hand, winnings, ParseError};
+use adv_2023_common::{input, Task};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Ord, PartialOrd)]
+#[repr(u8)]
+enum Card {
+    N2 = 0,
+    N3 = 1,
+    N4 = 2,
+    N5 = 3,
+    N6 = 4,
+    N7 = 5,
+    N8 = 6,
+    N9 = 7,
+    T = 8,
+    J = 9,
+    Q = 10,
+    K = 11,
+    A = 12,
+}
+
+struct ParsedLine {
+    hand: [Card; 5],
+    bid: u32,
+}
+
+impl ParsedLine {
+    pub fn parse(line: &str, lowercase: bool) -> Result<Self, ParseError> {
+        let (strengths, bid) = parse_hand(line, ORDER, lowercase)?;
+        let hand = strengths.map(|s| CARDS[s as usize]);
+        Ok(ParsedLine { hand, bid })
+    }
+}
+
+/// Card labels from weakest to strongest.
+static ORDER: &[u8; 13] = b"23456789TJQKA";
+
+static CARDS: [Card; 13] = [
+    Card::N2,
+    Card::N3,
+    Card::N4,
+    Card::N5,
+    Card::N6,
+    Card::N7,
+    Card::N8,
+    Card::N9,
+    Card::T,
+    Card::J,
+    Card::Q,
+    Card::K,
+    Card::A,
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Ord, PartialOrd)]
+#[repr(u8)]
+enum HandType {
+    HighCard = 0,
+    Pair = 1,
+    TwoPairs = 2,
+    Three = 3,
+    FullHouse = 4,
+    Four = 5,
+    Five = 6,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Ord, PartialOrd)]
+struct Hand(HandType, Card, Card, Card, Card, Card);
+
+impl Hand {
+    fn new(cards: [Card; 5]) -> Self {
+        let hand_type = Self::hand_type(&cards);
+        Hand(hand_type, cards[0], cards[1], cards[2], cards[3], cards[4])
+    }
+
+    fn packed(&self) -> u32 {
+        let Hand(hand_type, c0, c1, c2, c3, c4) = *self;
+        pack(
+            hand_type as u8,
+            [c0 as u8, c1 as u8, c2 as u8, c3 as u8, c4 as u8],
+        )
+    }
+
+    fn hand_type(cards: &[Card; 5]) -> HandType {
+        let mut counts: [u8; 13] = [0; 13];
+        for &card in cards {
+            counts[card as u8 as usize] += 1;
+        }
+        counts.sort_by(|c1, c2| c2.cmp(c1));
+        let mut counts = counts.into_iter();
+        let count1 = counts.next().unwrap();
+        if count1 == 5 {
+            return HandType::Five;
+        }
+        if count1 == 4 {
+            return HandType::Four;
+        }
+        let count2 = counts.next().unwrap();
+        if count1 == 3 && count2 == 2 {
+            return HandType::FullHouse;
+        }
+        if count1 == 3 {
+            return HandType::Three;
+        }
+        if count1 == 2 && count2 == 2 {
+            return HandType::TwoPairs;
+        }
+        if count1 == 2 {
+            return HandType::Pair;
+        }
+        HandType::HighCard
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Packed hands and their bids, in input order until sorted.
+    hands: Vec<(u32, u32)>,
+    /// Accept card labels in lower case as well.
+    lowercase: bool,
+    lines: usize,
+}
+
+impl Task for State {
+    type Input<'a> = Result<ParsedLine, ParseError> where Self: 'a;
+
+    type Output<'a> = u64 where Self: 'a;
+
+    fn parse<'a>(&self, line: &'a str) -> Self::Input<'a> {
+        ParsedLine::parse(line, self.lowercase)
+    }
+
+    fn process(&mut self, input: Self::Input<'_>) {
+        self.lines += 1;
+        let lines = self.lines;
+        let ParsedLine { hand, bid } =
+            input.unwrap_or_else(|e| panic!("Invalid hand: {}", e.at_line(lines)));
+        self.hands.push((Hand::new(hand).packed(), bid));
+    }
+
+    fn output(&mut self) -> Self::Output<'_> {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        par_radix_sort(&mut self.hands, threads);
+        if let Some((hand, bid1, bid2)) = duplicate(&self.hands) {
+            panic!("Duplicate hand: {:#07x} {:?} {:?}", hand, bid1, bid2);
+        }
+        winnings(&self.hands)
+    }
+}
+
+pub fn solve(path: &str) -> String {
+    State::default().run(path).to_string()
+}
+
+pub fn main() {
+    let mut state = State {
+        lowercase: std::env::args().any(|a| a == "--lowercase"),
+        ..Default::default()
+    };
+    let res = state.run(&input::arg("adv-2023-day7/input/list.txt"));
+    println!("{}", res);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adv_2023_common::gen::Rng;
+
+    #[test]
+    fn example() {
+        let mut state = State::default();
+        assert_eq!(state.run_str(include_str!("../input/example.txt")), 6440);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid hand: line 2, column 4: invalid card: 'x'")]
+    fn errors_name_the_line() {
+        State::default().run_str("32T3K 765\nT55x5 684\n");
+    }
+
+    #[test]
+    fn packed_hands_order_like_hands() {
+        let mut rng = Rng::new(7);
+        // few distinct cards, so that every hand type comes up
+        let mut hand = || Hand::new([(); 5].map(|_| CARDS[rng.below(4) as usize * 3]));
+        for _ in 0..10_000 {
+            let (a, b) = (hand(), hand());
+            assert_eq!(a.packed().cmp(&b.packed()), a.cmp(&b), "{:?} {:?}", a, b);
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! `#[derive(Task)]`, re-exported by `adv-2023-common` next to the trait it implements.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, ExprPath, LitStr, Type};
+
+/// Implement `Task` for a struct that keeps the state of a solution.
+///
+/// The struct is configured with `#[task(...)]`:
+///
+/// - `input = Type` is what a line is parsed into, and may borrow from the line as `'a`,
+/// - `parse = path` parses a line with `fn(&'a str) -> Type`, by default `Type::parse`,
+/// - `process = path` processes a parsed line with `fn(&mut Self, Type)`,
+/// - `output = path` and `output_type = Type` compute the result with `fn(&mut Self) -> Type`,
+///   unless a `Copy` field is marked with `#[task(output)]` to be the result,
+/// - `main = "path"` also generates `solve`, running the `Default` state on an input, and a
+///   `main` printing its result for the input given with `--input` or at `path`.
+///
+/// ```ignore
+/// #[derive(Default, Task)]
+/// #[task(input = ParsedLine, process = Self::add, main = "adv-2023-day8/input/list.txt")]
+/// struct State {
+///     #[task(output)]
+///     sum: u64,
+/// }
+/// ```
+#[proc_macro_derive(Task, attributes(task))]
+pub fn derive_task(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// How the result of a task is found.
+enum Output {
+    Field(TokenStream, Type),
+    Method(ExprPath, Type),
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.ident.span(),
+            "Task can only be derived for structs",
+        ));
+    };
+
+    let mut parsed = None;
+    let mut parse = None;
+    let mut process = None;
+    let mut output = None;
+    let mut output_type = None;
+    let mut main = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("task")) {
+        attr.parse_nested_meta(|meta| {
+            let key = meta.path.get_ident().map(ToString::to_string);
+            match key.as_deref() {
+                Some("input") => parsed = Some(meta.value()?.parse::<Type>()?),
+                Some("parse") => parse = Some(meta.value()?.parse::<ExprPath>()?),
+                Some("process") => process = Some(meta.value()?.parse::<ExprPath>()?),
+                Some("output") => output = Some(meta.value()?.parse::<ExprPath>()?),
+                Some("output_type") => output_type = Some(meta.value()?.parse::<Type>()?),
+                Some("main") => main = Some(meta.value()?.parse::<LitStr>()?),
+                _ => return Err(meta.error("unknown task attribute")),
+            }
+            Ok(())
+        })?;
+    }
+
+    let mut fields = Vec::new();
+    for (i, field) in data.fields.iter().enumerate() {
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("task")) {
+            attr.parse_nested_meta(|meta| {
+                if !meta.path.is_ident("output") {
+                    return Err(meta.error("expected `output`"));
+                }
+                let member = match &field.ident {
+                    Some(ident) => quote!(#ident),
+                    None => {
+                        let index = syn::Index::from(i);
+                        quote!(#index)
+                    }
+                };
+                fields.push((attr.span(), Output::Field(member, field.ty.clone())));
+                Ok(())
+            })?;
+        }
+    }
+
+    let span = input.ident.span();
+    let parsed = parsed.ok_or_else(|| Error::new(span, "missing `#[task(input = Type)]`"))?;
+    let process = process.ok_or_else(|| Error::new(span, "missing `#[task(process = path)]`"))?;
+    let output = match (output, output_type, fields.len()) {
+        (Some(method), Some(ty), 0) => Output::Method(method, ty),
+        (Some(method), None, 0) => {
+            return Err(Error::new(
+                method.span(),
+                "`output` needs the type it returns as `output_type`",
+            ))
+        }
+        (None, Some(ty), _) => {
+            return Err(Error::new(
+                ty.span(),
+                "`output_type` is only used with `output = path`",
+            ))
+        }
+        (None, None, 1) => fields.pop().expect("one field").1,
+        (None, None, 0) => {
+            return Err(Error::new(
+                span,
+                "missing `#[task(output = path)]` or a field with `#[task(output)]`",
+            ))
+        }
+        (_, _, _) => {
+            let (span, _) = fields.pop().expect("a field");
+            return Err(Error::new(span, "the output is given more than once"));
+        }
+    };
+
+    let parse = match parse {
+        Some(parse) => quote!(#parse),
+        None => quote!(<#parsed>::parse),
+    };
+    let (output_type, output) = match output {
+        Output::Field(member, ty) => (ty, quote!(self.#member)),
+        Output::Method(method, ty) => (ty, quote!(#method(self))),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut expanded = quote! {
+        impl #impl_generics ::adv_2023_common::Task for #name #ty_generics #where_clause {
+            type Input<'a> = #parsed where Self: 'a;
+
+            type Output<'a> = #output_type where Self: 'a;
+
+            fn parse<'a>(&self, line: &'a str) -> Self::Input<'a> {
+                #parse(line)
+            }
+
+            fn process(&mut self, input: Self::Input<'_>) {
+                #process(self, input)
+            }
+
+            fn output(&mut self) -> Self::Output<'_> {
+                #output
+            }
+        }
+    };
+
+    if let Some(path) = main {
+        if !input.generics.params.is_empty() {
+            return Err(Error::new(
+                path.span(),
+                "`main` needs a struct without generics",
+            ));
+        }
+        expanded.extend(quote! {
+            pub fn solve(path: &str) -> String {
+                let mut state = <#name as ::core::default::Default>::default();
+                ::adv_2023_common::Task::run(&mut state, path).to_string()
+            }
+
+            pub fn main() {
+                println!("{}", solve(&::adv_2023_common::input::arg(#path)));
+            }
+        });
+    }
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn error(input: DeriveInput) -> String {
+        expand(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn expands_to_an_impl_and_main() {
+        let expanded = expand(parse_quote! {
+            #[task(input = Line<'a>, parse = Line::new, process = Self::add)]
+            #[task(main = "adv-2023-day8/input/list.txt")]
+            struct State {
+                #[task(output)]
+                sum: u64,
+            }
+        })
+        .unwrap()
+        .to_string();
+        assert!(expanded.contains("type Input < 'a > = Line < 'a > where Self : 'a ;"));
+        assert!(expanded.contains("type Output < 'a > = u64 where Self : 'a ;"));
+        assert!(expanded.contains("Line :: new (line)"));
+        assert!(expanded.contains("Self :: add (self , input)"));
+        assert!(expanded.contains("self . sum"));
+        assert!(expanded.contains("pub fn main ()"));
+    }
+
+    #[test]
+    fn parse_defaults_to_the_input_type() {
+        let expanded = expand(parse_quote! {
+            #[task(input = Line, process = Self::add, output = Self::total, output_type = u64)]
+            struct State(u64);
+        })
+        .unwrap()
+        .to_string();
+        assert!(expanded.contains("< Line > :: parse (line)"));
+        assert!(expanded.contains("Self :: total (self)"));
+        assert!(!expanded.contains("fn main"));
+    }
+
+    #[test]
+    fn reports_missing_and_conflicting_attributes() {
+        assert_eq!(
+            error(parse_quote! {
+                #[task(process = Self::add)]
+                struct State(#[task(output)] u64);
+            }),
+            "missing `#[task(input = Type)]`"
+        );
+        assert_eq!(
+            error(parse_quote! {
+                #[task(input = Line, process = Self::add)]
+                struct State(u64);
+            }),
+            "missing `#[task(output = path)]` or a field with `#[task(output)]`"
+        );
+        assert_eq!(
+            error(parse_quote! {
+                #[task(input = Line, process = Self::add, output = Self::total)]
+                struct State(u64);
+            }),
+            "`output` needs the type it returns as `output_type`"
+        );
+        assert_eq!(
+            error(parse_quote! {
+                #[task(input = Line, process = Self::add)]
+                struct State(#[task(output)] u64, #[task(output)] u64);
+            }),
+            "the output is given more than once"
+        );
+        assert_eq!(
+            error(parse_quote! {
+                #[task(input = Line, process = Self::add, outptu = Self::total)]
+                struct State(#[task(output)] u64);
+            }),
+            "unknown task attribute"
+        );
+    }
+}
@@ -0,0 +1,137 @@
+mod pool;
+mod report;
+mod scaffold;
+mod solutions;
+
+use std::{fs::File, io::Write, path::Path};
+
+use adv_2023_common::{
+    gen::{self, Config},
+    json::ToJson,
+};
+use report::Report;
+use solutions::SOLUTIONS;
+
+const USAGE: &str = "\
+Usage: adv-2023 <command> [options]
+
+Commands:
+    run [dayN | dayN/PART]... [--jobs N] [--json | --jsonl]
+        Solve the given days and parts, or all of them, on N threads and report the results as
+        text, a JSON array or one JSON object per line.
+    gen dayN [--size N] [--seed S] [--edge-cases] [--output PATH]
+        Write a generated input for day N to stdout or PATH.
+    new-day dayN
+        Create the crate for day N from a template and add it to the workspace and the runner.";
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("gen") => gen(&args[1..]),
+        Some("new-day") => new_day(&args[1..]),
+        Some("help") | Some("--help") | None => println!("{}", USAGE),
+        Some(cmd) => {
+            eprintln!("Unknown command: {:?}\n\n{}", cmd, USAGE);
+            std::process::exit(2);
+        }
+    }
+}
+
+/// The value following `flag`, if given.
+fn flag<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .skip_while(|a| *a != flag)
+        .nth(1)
+        .map(String::as_str)
+}
+
+/// Parse a day such as `day7` or `7`.
+fn parse_day(arg: &str) -> u8 {
+    arg.strip_prefix("day")
+        .unwrap_or(arg)
+        .parse::<u8>()
+        .unwrap_or_else(|_| panic!("Invalid day: {:?}", arg))
+}
+
+fn run(args: &[String]) {
+    let selected = args
+        .iter()
+        .enumerate()
+        // skip flags and their values
+        .filter(|&(i, a)| !a.starts_with("--") && (i == 0 || args[i - 1] != "--jobs"))
+        .map(|(_, a)| a)
+        .map(|a| match a.split_once('/') {
+            Some((day, part)) => (parse_day(day), Some(part.parse::<u8>().unwrap())),
+            None => (parse_day(a), None),
+        })
+        .collect::<Vec<_>>();
+    let solutions = SOLUTIONS
+        .iter()
+        .filter(|s| {
+            selected.is_empty()
+                || selected
+                    .iter()
+                    .any(|&(day, part)| s.day == day && part.is_none_or(|p| s.part == p))
+        })
+        .collect::<Vec<_>>();
+    let jobs = flag(args, "--jobs").map_or_else(
+        || std::thread::available_parallelism().map_or(1, |n| n.get()),
+        |n| n.parse::<usize>().unwrap(),
+    );
+    let reports = pool::map(&solutions, jobs, |s| Report::run(s));
+
+    if args.iter().any(|a| a == "--json") {
+        println!("{}", reports.to_json());
+    } else if args.iter().any(|a| a == "--jsonl") {
+        for report in &reports {
+            println!("{}", report.to_json());
+        }
+    } else {
+        for report in &reports {
+            println!("{}", report);
+        }
+    }
+    if reports.iter().any(|r| r.output.is_err()) {
+        std::process::exit(1);
+    }
+}
+
+fn gen(args: &[String]) {
+    let day = parse_day(args.first().map_or("", String::as_str));
+    let mut config = Config::default();
+    if let Some(size) = flag(args, "--size") {
+        config.size = size.parse().unwrap();
+    }
+    if let Some(seed) = flag(args, "--seed") {
+        config.seed = seed.parse().unwrap();
+    }
+    config.edge_cases = args.iter().any(|a| a == "--edge-cases");
+
+    let input = gen::generate(day, &config)
+        .unwrap_or_else(|| panic!("No generator for day {}, try one of {:?}", day, gen::DAYS));
+    match flag(args, "--output") {
+        Some(path) => File::create(path)
+            .and_then(|mut f| f.write_all(input.as_bytes()))
+            .unwrap_or_else(|e| panic!("Could not write {:?}: {}", path, e)),
+        None => print!("{}", input),
+    }
+}
+
+fn new_day(args: &[String]) {
+    let day = parse_day(args.first().map_or("", String::as_str));
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the runner is in the workspace");
+    let written = scaffold::new_day(root, day).unwrap_or_else(|e| {
+        eprintln!("Could not create day {}: {}", day, e);
+        std::process::exit(1);
+    });
+    for path in written {
+        println!("{}", path.display());
+    }
+    eprintln!(
+        "Put the example in adv-2023-day{0}/input/example.txt and your input in adv-2023-day{0}/input/list.txt.",
+        day
+    );
+}
@@ -0,0 +1,125 @@
+use std::{
+    cell::RefCell,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, Once,
+    },
+};
+
+/// Apply `f` to every item on up to `jobs` threads, returning the results in the order of the
+/// items regardless of the order they finished in.
+pub fn map<T: Sync, R: Send>(items: &[T], jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let jobs = jobs.clamp(1, items.len().max(1));
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<_>>());
+    std::thread::scope(|s| {
+        for _ in 0..jobs {
+            s.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else {
+                    break;
+                };
+                let result = f(item);
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.expect("every item is mapped"))
+        .collect()
+}
+
+thread_local! {
+    static PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Run `f`, turning a panic into the message and location it panicked with.
+///
+/// The message is kept instead of printed, so panics on different threads do not interleave.
+/// A panic passed on from a thread that `f` spawned has already been printed there, and only its
+/// message is kept.
+pub fn catch<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let caught = PANIC.with(|p| {
+                let mut p = p.borrow_mut();
+                if p.is_some() {
+                    let message = info.payload_as_str().unwrap_or("Box<dyn Any>");
+                    *p = Some(match info.location() {
+                        Some(location) => format!("{} at {}", message, location),
+                        None => message.to_string(),
+                    });
+                }
+                p.is_some()
+            });
+            if !caught {
+                default(info);
+            }
+        }));
+    });
+    // an empty message marks that a panic on this thread is expected
+    PANIC.with(|p| *p.borrow_mut() = Some(String::new()));
+    let result = catch_unwind(AssertUnwindSafe(f));
+    let message = PANIC.with(|p| p.borrow_mut().take()).unwrap_or_default();
+    result.map_err(|payload| {
+        if !message.is_empty() {
+            return message;
+        }
+        // resuming a panic from another thread does not go through the hook
+        payload
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+            .unwrap_or_else(|| "Box<dyn Any>".to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_keeps_the_order_of_the_items() {
+        let items: Vec<u64> = (0..50).collect();
+        // later items finish first
+        let squares = map(&items, 8, |&n| {
+            std::thread::sleep(std::time::Duration::from_micros(50 - n));
+            n * n
+        });
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn catch_keeps_panics_apart() {
+        let results = map(&[1, 2, 3, 4], 4, |&n| {
+            catch(|| {
+                if n % 2 == 0 {
+                    panic!("even: {}", n);
+                }
+                n
+            })
+        });
+        assert_eq!(results[0], Ok(1));
+        assert!(results[1].as_ref().unwrap_err().starts_with("even: 2 at "));
+        assert_eq!(results[2], Ok(3));
+        assert!(results[3].as_ref().unwrap_err().starts_with("even: 4 at "));
+    }
+
+    #[test]
+    fn catch_keeps_panics_from_spawned_threads() {
+        let result = catch(|| {
+            std::thread::scope(|s| {
+                s.spawn(|| panic!("in a worker: {}", 7))
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e))
+            })
+        });
+        assert_eq!(result, Err("in a worker: 7".to_string()));
+    }
+}
@@ -0,0 +1,90 @@
+use std::{fmt::Display, path::Path, time::Instant};
+
+use adv_2023_common::{
+    input,
+    json::{Object, ToJson},
+};
+
+use crate::{pool, solutions::Solution};
+
+/// The outcome of running a single solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    /// FNV-1a hash of the input, to tell which input a result belongs to.
+    pub input_hash: Option<u64>,
+    pub output: Result<String, String>,
+    pub elapsed_us: u64,
+}
+
+impl Report {
+    pub fn run(solution: &Solution) -> Self {
+        let input = solution.input();
+        let mut report = Self {
+            day: solution.day,
+            part: solution.part,
+            input,
+            input_hash: None,
+            output: Err(String::new()),
+            elapsed_us: 0,
+        };
+        let path = match input::resolve(Path::new(&report.input)) {
+            Ok(path) => path,
+            Err(e) => {
+                report.output = Err(e.to_string());
+                return report;
+            }
+        };
+        report.input = path.display().to_string();
+        match std::fs::read(&path) {
+            Ok(bytes) => report.input_hash = Some(fnv1a(&bytes)),
+            Err(e) => {
+                report.output = Err(format!("Failed to read {:?}: {}", report.input, e));
+                return report;
+            }
+        }
+        let time = Instant::now();
+        report.output =
+            pool::catch(|| (solution.solve)(&report.input)).map_err(|e| format!("panicked: {}", e));
+        report.elapsed_us = time.elapsed().as_micros() as u64;
+        report
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.output {
+            Ok(output) => write!(
+                f,
+                "day {} part {}: {} ({}us)",
+                self.day, self.part, output, self.elapsed_us
+            ),
+            Err(e) => write!(f, "day {} part {}: error: {}", self.day, self.part, e),
+        }
+    }
+}
+
+impl ToJson for Report {
+    fn write_json(&self, out: &mut String) {
+        Object::new(out)
+            .field("day", &self.day)
+            .field("part", &self.part)
+            .field("input", &self.input)
+            .field(
+                "input_hash",
+                &self.input_hash.map(|h| format!("{:016x}", h)),
+            )
+            .field("output", &self.output.as_ref().ok())
+            .field("error", &self.output.as_ref().err())
+            .field("elapsed_us", &self.elapsed_us)
+            .finish();
+    }
+}
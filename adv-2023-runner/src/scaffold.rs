@@ -0,0 +1,232 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+const CARGO_TOML: &str = r#"[package]
+name = "adv-2023-day{day}"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+[[bin]]
+name = "adv-2023-day{day}-task1"
+path = "src/bin/task1.rs"
+
+[[bin]]
+name = "adv-2023-day{day}-task2"
+path = "src/bin/task2.rs"
+
+[dependencies]
+adv-2023-common = { path = "../adv-2023-common" }
+"#;
+
+const LIB: &str = "pub mod task1;
+pub mod task2;
+";
+
+const BIN: &str = "fn main() {
+    adv_2023_day{day}::task{part}::main();
+}
+";
+
+const TASK: &str = r#"use adv_2023_common::Task;
+
+struct ParsedLine {
+    value: u64,
+}
+
+impl ParsedLine {
+    pub fn parse(line: &str) -> Self {
+        todo!("parse {:?}", line)
+    }
+}
+
+#[derive(Debug, Default, Task)]
+#[task(input = ParsedLine, process = Self::add, main = "adv-2023-day{day}/input/list.txt")]
+struct State {
+    #[task(output)]
+    total: u64,
+}
+
+impl State {
+    fn add(&mut self, line: ParsedLine) {
+        self.total += line.value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore = "the answer to the example is not filled in yet"]
+    fn example() {
+        let mut state = State::default();
+        assert_eq!(state.run_str(include_str!("../input/example.txt")), 0);
+    }
+}
+"#;
+
+/// Create the crate for `day` in the workspace at `root` and register it with the workspace and
+/// the runner, returning the files that were written.
+///
+/// Nothing is written if the crate already exists.
+pub fn new_day(root: &Path, day: u8) -> io::Result<Vec<PathBuf>> {
+    let name = format!("adv-2023-day{}", day);
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!("{} already exists", dir.display()),
+        ));
+    }
+    let fill = |template: &str, part: u8| {
+        template
+            .replace("{day}", &day.to_string())
+            .replace("{part}", &part.to_string())
+    };
+    let files = [
+        ("Cargo.toml", fill(CARGO_TOML, 0)),
+        ("src/lib.rs", LIB.to_string()),
+        ("src/task1.rs", fill(TASK, 1)),
+        ("src/task2.rs", fill(TASK, 2)),
+        ("src/bin/task1.rs", fill(BIN, 1)),
+        ("src/bin/task2.rs", fill(BIN, 2)),
+        ("input/example.txt", String::new()),
+    ];
+    // edit the existing files first, so a failure leaves no half-registered crate behind
+    let edits = [
+        (
+            root.join("Cargo.toml"),
+            format!("    \"{}\",", name),
+            "\"adv-2023-common\"",
+        ),
+        (
+            root.join("adv-2023-runner/Cargo.toml"),
+            format!("{} = {{ path = \"../{}\" }}", name, name),
+            "adv-2023-common ",
+        ),
+        (
+            root.join("adv-2023-runner/src/solutions.rs"),
+            (1..=2)
+                .map(|part| {
+                    format!(
+                        "    solution({}, {}, adv_2023_day{}::task{}::solve),",
+                        day, part, day, part
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "pub static SOLUTIONS",
+        ),
+    ]
+    .into_iter()
+    .map(|(path, entry, fallback)| {
+        let text = fs::read_to_string(&path)?;
+        let text = register(&text, day, &entry, fallback).ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::InvalidData,
+                format!("found no place for {:?} in {}", entry, path.display()),
+            )
+        })?;
+        Ok((path, text))
+    })
+    .collect::<io::Result<Vec<_>>>()?;
+
+    let mut written = Vec::with_capacity(files.len() + edits.len());
+    for (file, contents) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().expect("files are in the crate directory"))?;
+        fs::write(&path, contents)?;
+        written.push(path);
+    }
+    for (path, text) in edits {
+        fs::write(&path, text)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// The day named by a line such as `"adv-2023-day7",` or `solution(7, 1, adv_2023_day7::...)`.
+fn day_of(line: &str) -> Option<u8> {
+    line.split(|c: char| !c.is_ascii_alphanumeric())
+        .find_map(|word| word.strip_prefix("day")?.parse().ok())
+}
+
+/// Insert `entry` after the last line for a day before `day`, or after the first line containing
+/// `fallback` if there is none, keeping lists sorted by day.
+fn register(text: &str, day: u8, entry: &str, fallback: &str) -> Option<String> {
+    let lines = text.lines().collect::<Vec<_>>();
+    let pos = lines
+        .iter()
+        .rposition(|l| day_of(l).is_some_and(|d| d < day))
+        .or_else(|| lines.iter().position(|l| l.contains(fallback)))?;
+    let mut out = lines[..=pos].join("\n");
+    out.push('\n');
+    out.push_str(entry);
+    for line in &lines[pos + 1..] {
+        out.push('\n');
+        out.push_str(line);
+    }
+    if text.ends_with('\n') {
+        out.push('\n');
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_keeps_days_in_order() {
+        let members = "members = [\n    \"adv-2023-common\",\n    \"adv-2023-day7\",\n    \"adv-2023-day9\",\n    \"adv-2023-runner\",\n]\n";
+        let entry = "    \"adv-2023-day8\",";
+        assert_eq!(
+            register(members, 8, entry, "\"adv-2023-common\"").unwrap(),
+            "members = [\n    \"adv-2023-common\",\n    \"adv-2023-day7\",\n    \"adv-2023-day8\",\n    \"adv-2023-day9\",\n    \"adv-2023-runner\",\n]\n"
+        );
+        let entry = "    \"adv-2023-day1\",";
+        assert_eq!(
+            register(members, 1, entry, "\"adv-2023-common\"").unwrap(),
+            "members = [\n    \"adv-2023-common\",\n    \"adv-2023-day1\",\n    \"adv-2023-day7\",\n    \"adv-2023-day9\",\n    \"adv-2023-runner\",\n]\n"
+        );
+        assert_eq!(
+            register("[dependencies]\n", 1, "x", "adv-2023-common"),
+            None
+        );
+    }
+
+    #[test]
+    fn new_day_registers_the_crate() {
+        let root = std::env::temp_dir().join(format!("aoc-new-day-{}", std::process::id()));
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        fs::create_dir_all(root.join("adv-2023-runner/src")).unwrap();
+        for file in [
+            "Cargo.toml",
+            "adv-2023-runner/Cargo.toml",
+            "adv-2023-runner/src/solutions.rs",
+        ] {
+            fs::copy(workspace.join(file), root.join(file)).unwrap();
+        }
+        let written = new_day(&root, 42).unwrap();
+        let again = new_day(&root, 42).map_err(|e| e.kind());
+        let read = |file: &str| fs::read_to_string(root.join(file)).unwrap();
+        let (members, deps, solutions) = (
+            read("Cargo.toml"),
+            read("adv-2023-runner/Cargo.toml"),
+            read("adv-2023-runner/src/solutions.rs"),
+        );
+        let bin = read("adv-2023-day42/src/bin/task2.rs");
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(written.len(), 10);
+        assert_eq!(again, Err(ErrorKind::AlreadyExists));
+        assert!(members.contains("    \"adv-2023-day7\",\n    \"adv-2023-day42\",\n"));
+        assert!(deps.ends_with("adv-2023-day42 = { path = \"../adv-2023-day42\" }\n"));
+        assert!(solutions.contains("    solution(42, 2, adv_2023_day42::task2::solve),\n];"));
+        assert!(bin.contains("adv_2023_day42::task2::main();"));
+    }
+}
@@ -0,0 +1,37 @@
+/// A part of a day that the runner can solve in-process.
+pub struct Solution {
+    pub day: u8,
+    pub part: u8,
+    /// Solve the puzzle for the input at the given path with the default options, on the calling
+    /// thread so that `--jobs` is all the parallelism there is.
+    pub solve: fn(&str) -> String,
+}
+
+impl Solution {
+    /// The path of the puzzle input, relative to the workspace.
+    pub fn input(&self) -> String {
+        format!("adv-2023-day{}/input/list.txt", self.day)
+    }
+}
+
+const fn solution(day: u8, part: u8, solve: fn(&str) -> String) -> Solution {
+    Solution { day, part, solve }
+}
+
+/// Every solution, ordered by day and part.
+pub static SOLUTIONS: &[Solution] = &[
+    solution(1, 1, adv_2023_day1::task1::solve),
+    solution(1, 2, adv_2023_day1::task2::solve),
+    solution(2, 1, adv_2023_day2::task1::solve),
+    solution(2, 2, adv_2023_day2::task2::solve),
+    solution(3, 1, adv_2023_day3::task1::solve),
+    solution(3, 2, adv_2023_day3::task2::solve),
+    solution(4, 1, adv_2023_day4::task1::solve),
+    solution(4, 2, adv_2023_day4::task2::solve),
+    solution(5, 1, adv_2023_day5::task1::solve),
+    solution(5, 2, adv_2023_day5::task2::solve),
+    solution(6, 1, adv_2023_day6::task1::solve),
+    solution(6, 2, adv_2023_day6::task2::solve),
+    solution(7, 1, adv_2023_day7::task1::solve),
+    solution(7, 2, adv_2023_day7::task2::solve),
+];